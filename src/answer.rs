@@ -0,0 +1,179 @@
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Integer(u64),
+    Signed(i64),
+    Big(u128),
+    Text(String),
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_numeric(&self) -> bool {
+        match self {
+            Answer::Integer(_) | Answer::Signed(_) | Answer::Big(_) => true,
+            Answer::Text(text) => integer(text).is_some(),
+            Answer::Grid(_) => false,
+        }
+    }
+
+    fn number(&self) -> Option<(bool, u128)> {
+        match self {
+            Answer::Integer(value) => Some((false, *value as u128)),
+            Answer::Signed(value) => Some((*value < 0, value.unsigned_abs() as u128)),
+            Answer::Big(value) => Some((false, *value)),
+            Answer::Text(text) if text.trim() == text => {
+                let (neg, digits) = integer(text)?;
+                match digits {
+                    "" => Some((false, 0)),
+                    digits => Some((neg, digits.parse().ok()?)),
+                }
+            }
+            Answer::Text(_) | Answer::Grid(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, rhs: &Self) -> bool {
+        match (self, rhs) {
+            (Answer::Text(left), Answer::Text(right)) => left == right,
+            (Answer::Grid(left), Answer::Grid(right)) => left == right,
+            (Answer::Text(_) | Answer::Grid(_), Answer::Text(_) | Answer::Grid(_)) => false,
+            _ => match (self.number(), rhs.number()) {
+                (Some(left), Some(right)) => left == right,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{value}"),
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Big(value) => write!(f, "{value}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Grid(rows) => {
+                for (i, row) in rows.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{row}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<Answer> for String {
+    fn from(answer: Answer) -> Self {
+        match answer {
+            Answer::Text(text) => text,
+            answer => answer.to_string(),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Integer(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Integer(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Integer(value as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        Answer::Big(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Self {
+        Answer::Grid(rows)
+    }
+}
+
+pub(crate) fn integer(text: &str) -> Option<(bool, &str)> {
+    let text = text.trim();
+    let (neg, digits) = match text.as_bytes().first()? {
+        b'-' => (true, &text[1..]),
+        b'+' => (false, &text[1..]),
+        _ => (false, text),
+    };
+    if digits.is_empty() || !digits.bytes().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let digits = digits.trim_start_matches('0');
+    Some((neg && !digits.is_empty(), digits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(42u64).to_string(), "42");
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        let grid = Answer::from(vec!["#.".to_string(), ".#".to_string()]);
+        assert_eq!(grid.to_string(), "#.\n.#");
+        assert_eq!(String::from(grid), "#.\n.#");
+    }
+
+    #[test]
+    fn eq() {
+        assert_eq!(Answer::from(42u64), Answer::from("42"));
+        assert_eq!(Answer::from(42u64), Answer::from(42u128));
+        assert_eq!(Answer::from(0u64), Answer::from(-0i64));
+        assert_eq!(Answer::from(-1i64), Answer::from("-01"));
+        assert_ne!(Answer::from(1i64), Answer::from(-1i64));
+        assert_ne!(Answer::from("abc"), Answer::from("ABC"));
+        assert_eq!(Answer::from(u128::MAX), Answer::from(u128::MAX.to_string()));
+        assert_ne!(Answer::from(u64::MAX), Answer::from(-1i64));
+        assert_ne!(Answer::from("042"), Answer::from("42"));
+        assert_eq!(Answer::from("042"), Answer::from(42u64));
+        assert_ne!(Answer::from(" 5"), Answer::from(5u64));
+        assert_ne!(Answer::from("5\n"), Answer::from(5u64));
+        let grid = Answer::from(vec!["a".to_string(), "b".to_string()]);
+        assert_ne!(Answer::from("a\nb"), grid);
+        assert_eq!(grid, Answer::from(vec!["a".to_string(), "b".to_string()]));
+        assert_ne!(grid, Answer::from(vec!["a".to_string()]));
+        assert!(Answer::from("+7").is_numeric());
+        assert!(!Answer::from("7a").is_numeric());
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use super::{offset, Answer, ErrorKind, ParseError};
+
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.txt";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u16, u8, u8), String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict<'a> {
+    Match,
+    Mismatch(&'a str),
+    Missing,
+    Unsupported,
+}
+
+impl Answers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn parse(input: &str) -> Result<Self, ParseError<'_>> {
+        let mut res = Self::new();
+        for line in input.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut rest = line;
+            let mut key = [0u16; 3];
+            for (j, value) in key.iter_mut().enumerate() {
+                let (field, tail) = rest
+                    .split_once([' ', '\t'])
+                    .unwrap_or((rest, &rest[rest.len()..]));
+                let expect = match j {
+                    0 => 2015..=u16::MAX,
+                    1 => 1..=25,
+                    _ => 1..=u8::MAX as u16,
+                };
+                let start = offset(input, field);
+                let span = start..start + field.len();
+                *value = match field.parse() {
+                    Ok(value) if expect.contains(&value) => value,
+                    Ok(_) => return Err(ParseError::new(ErrorKind::OutOfRange, input, span)),
+                    Err(err) => {
+                        let kind = ErrorKind::NumberFormat;
+                        return Err(ParseError::new(kind, input, span).with_source(err));
+                    }
+                };
+                rest = tail.trim_start_matches([' ', '\t']);
+            }
+            if rest.is_empty() {
+                let end = offset(input, rest);
+                return Err(ParseError::new(ErrorKind::UnexpectedEnd, input, end..end)
+                    .with_msg("Expected answer"));
+            }
+            res.insert(key[0], key[1] as u8, key[2] as u8, rest);
+        }
+        Ok(res)
+    }
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.entries.get(&(year, day, part)).map(String::as_str)
+    }
+    pub fn insert(&mut self, year: u16, day: u8, part: u8, answer: &str) {
+        self.entries.insert((year, day, part), answer.to_string());
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    pub fn verify(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &Answer,
+        numeric: bool,
+    ) -> Verdict<'_> {
+        let expect = match self.get(year, day, part) {
+            None => return Verdict::Missing,
+            Some(_) if matches!(answer, Answer::Grid(_)) => return Verdict::Unsupported,
+            Some(expect) => expect,
+        };
+        let same = match numeric {
+            true => Answer::from(expect) == *answer,
+            false => expect == answer.to_string(),
+        };
+        match same {
+            true => Verdict::Match,
+            false => Verdict::Mismatch(expect),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bad() {
+        for input in [
+            "2015",
+            "2015 1",
+            "2015 1 1",
+            "2014 1 1 x",
+            "2015 26 1 x",
+            "x 1 1 2",
+        ] {
+            assert!(Answers::parse(input).is_err());
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse("# comment\n\n2015 1 1 74\n2015\t1  2 Hello world\n").unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers.get(2015, 1, 1), Some("74"));
+        assert_eq!(answers.get(2015, 1, 2), Some("Hello world"));
+        assert_eq!(answers.get(2015, 2, 1), None);
+        let err = Answers::parse("2015 1 1 1\n2015 1 x 2").unwrap_err();
+        assert_eq!((err.line(), err.pos(), err.arg()), (2, 8, "x"));
+        assert_eq!(err.kind(), ErrorKind::NumberFormat);
+        let err = Answers::parse("2015 26 1 x").unwrap_err();
+        assert_eq!((err.kind(), err.pos()), (ErrorKind::OutOfRange, 6));
+        let err = Answers::parse("2015 1 1").unwrap_err();
+        assert_eq!(
+            (err.kind(), err.msg()),
+            (ErrorKind::UnexpectedEnd, "Expected answer")
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let answers = Answers::parse("2015 1 1 074\n2015 1 2 -0").unwrap();
+        let verify =
+            |day, part, answer: Answer, numeric| answers.verify(2015, day, part, &answer, numeric);
+        assert_eq!(verify(1, 1, "074".into(), false), Verdict::Match);
+        assert_eq!(verify(1, 1, 74u64.into(), false), Verdict::Mismatch("074"));
+        assert_eq!(verify(1, 1, 74u64.into(), true), Verdict::Match);
+        assert_eq!(verify(1, 1, 75u64.into(), true), Verdict::Mismatch("074"));
+        assert_eq!(verify(1, 2, 0i64.into(), true), Verdict::Match);
+        assert_eq!(verify(2, 1, 0i64.into(), true), Verdict::Missing);
+        assert_eq!(verify(1, 1, "74".into(), true), Verdict::Mismatch("074"));
+        let grid = Answer::from(vec!["#.".to_string(), ".#".to_string()]);
+        assert_eq!(
+            answers.verify(2015, 1, 1, &grid, false),
+            Verdict::Unsupported
+        );
+        assert_eq!(answers.verify(2015, 3, 1, &grid, true), Verdict::Missing);
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use std::{error, fmt};
+
+use super::{offset, Answer, Error, ErrorKind, ParseError, ParseOptions, Problem, SolveContext};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    runs: usize,
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        let runs = samples.len();
+        if runs == 0 {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let median = if runs % 2 == 1 {
+            sorted[runs / 2]
+        } else {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        };
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / runs as f64;
+        let var = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        Some(Stats {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(var.sqrt()),
+        })
+    }
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+    pub fn median(&self) -> Duration {
+        self.median
+    }
+    pub fn mean(&self) -> Duration {
+        self.mean
+    }
+    pub fn stddev(&self) -> Duration {
+        self.stddev
+    }
+}
+
+pub struct Bench {
+    problem: &'static Problem,
+    parse: Stats,
+    parts: Vec<(u8, Stats)>,
+    answers: Vec<Answer>,
+}
+
+impl Bench {
+    pub fn problem(&self) -> &'static Problem {
+        self.problem
+    }
+    pub fn parse(&self) -> &Stats {
+        &self.parse
+    }
+    pub fn parts(&self) -> &[(u8, Stats)] {
+        &self.parts
+    }
+    pub fn answers(&self) -> &[Answer] {
+        &self.answers
+    }
+}
+
+pub fn bench(
+    problem: &'static Problem,
+    input: &str,
+    warmup: usize,
+    runs: usize,
+    options: &ParseOptions,
+    ctx: &SolveContext,
+) -> Result<Bench, Error> {
+    let runs = runs.max(1);
+    let parts = problem.parts() as usize;
+    let mut parse = Vec::with_capacity(runs);
+    let mut samples = vec![Vec::with_capacity(runs); parts];
+    let mut answers = Vec::with_capacity(parts);
+    for run in 0..warmup + runs {
+        let start = Instant::now();
+        let mut solver = problem.parse_with(input, options)?;
+        let time = start.elapsed();
+        if run >= warmup {
+            parse.push(time);
+        }
+        for (part, samples) in (1..).zip(&mut samples) {
+            let start = Instant::now();
+            let answer = solver.solve_with(part, &ctx.start())?;
+            let time = start.elapsed();
+            if run >= warmup {
+                samples.push(time);
+            }
+            if run + 1 == warmup + runs {
+                answers.push(answer);
+            }
+        }
+    }
+    let stats = |samples: &[Duration]| Stats::new(samples).unwrap();
+    Ok(Bench {
+        problem,
+        parse: stats(&parse),
+        parts: (1..).zip(samples).map(|(i, s)| (i, stats(&s))).collect(),
+        answers,
+    })
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    entries: BTreeMap<(u16, u8, u8), Duration>,
+}
+
+impl Baseline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn parse(input: &str) -> Result<Self, ParseError<'_>> {
+        let mut res = Self::new();
+        for line in input.lines() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let end = offset(input, line) + line.len();
+            if fields.len() < 4 {
+                return Err(ParseError::new(ErrorKind::UnexpectedEnd, input, end..end)
+                    .with_msg("Expected YEAR DAY PART NANOS"));
+            }
+            if let Some(field) = fields.get(4) {
+                let start = offset(input, field);
+                return Err(ParseError::new(ErrorKind::TrailingData, input, start..end));
+            }
+            let year = field(input, fields[0])?;
+            let day = field(input, fields[1])?;
+            let part = match fields[2] {
+                "parse" => 0,
+                part => field(input, part)?,
+            };
+            let nanos = field(input, fields[3])?;
+            res.entries
+                .insert((year, day, part), Duration::from_nanos(nanos));
+        }
+        Ok(res)
+    }
+    pub fn insert(&mut self, bench: &Bench) {
+        let problem = bench.problem();
+        let (year, day) = (problem.year(), problem.day());
+        self.entries.insert((year, day, 0), bench.parse().median());
+        for (part, stats) in bench.parts() {
+            self.entries.insert((year, day, *part), stats.median());
+        }
+    }
+    pub fn get(&self, year: u16, day: u8, part: Option<u8>) -> Option<Duration> {
+        self.entries.get(&(year, day, part.unwrap_or(0))).copied()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (&(year, day, stage), median) in &self.entries {
+            match stage {
+                0 => write!(f, "{year} {day} parse")?,
+                _ => write!(f, "{year} {day} {stage}")?,
+            }
+            writeln!(f, " {}", median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+fn field<'a, T>(input: &'a str, field: &'a str) -> Result<T, ParseError<'a>>
+where
+    T: FromStr,
+    T::Err: error::Error + Send + Sync + 'static,
+{
+    field.parse().map_err(|err| {
+        let start = offset(input, field);
+        ParseError::new(ErrorKind::NumberFormat, input, start..start + field.len()).with_source(err)
+    })
+}
+
+pub fn change(baseline: Duration, current: Duration) -> f64 {
+    let baseline = baseline.as_secs_f64();
+    if baseline == 0.0 {
+        return 0.0;
+    }
+    current.as_secs_f64() / baseline - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn stats() {
+        assert_eq!(Stats::new(&[]), None);
+        let stats = Stats::new(&ms(&[4, 2, 9, 5])).unwrap();
+        assert_eq!(stats.runs(), 4);
+        assert_eq!(stats.min(), Duration::from_millis(2));
+        assert_eq!(stats.median(), Duration::from_micros(4500));
+        assert_eq!(stats.mean(), Duration::from_millis(5));
+        let stats = Stats::new(&ms(&[2, 4, 4, 4, 5, 5, 7, 9])).unwrap();
+        assert_eq!(stats.median(), Duration::from_micros(4500));
+        assert_eq!(stats.stddev(), Duration::from_millis(2));
+    }
+
+    #[test]
+    fn bench() {
+        let (options, ctx) = (ParseOptions::new(), SolveContext::new());
+        let bench = super::bench(&crate::PROBLEMS[0], "())", 1, 3, &options, &ctx).unwrap();
+        assert_eq!(bench.parse().runs(), 3);
+        assert_eq!(bench.parts().len(), 2);
+        assert_eq!(bench.answers(), [(-1i64).into(), 3u64.into()]);
+        assert!(super::bench(&crate::PROBLEMS[0], "(x", 1, 3, &options, &ctx).is_err());
+    }
+
+    #[test]
+    fn baseline() {
+        let text = "2015 1 parse 1500\n2015 1 1 200\n2015 1 2 300\n";
+        let baseline = Baseline::parse(text).unwrap();
+        assert_eq!(
+            baseline.get(2015, 1, None),
+            Some(Duration::from_nanos(1500))
+        );
+        assert_eq!(
+            baseline.get(2015, 1, Some(2)),
+            Some(Duration::from_nanos(300))
+        );
+        assert_eq!(baseline.get(2015, 2, Some(1)), None);
+        assert_eq!(baseline.to_string(), text);
+        for input in ["2015 1 1", "2015 1 x 3", "2015 1 1 3 4", "2015 256 1 3"] {
+            assert!(Baseline::parse(input).is_err());
+        }
+        let err = Baseline::parse("2015 1 1 x").unwrap_err();
+        assert_eq!((err.line(), err.pos(), err.arg()), (1, 10, "x"));
+        assert_eq!(err.kind(), ErrorKind::NumberFormat);
+        let err = Baseline::parse("2015 1 1 3 4").unwrap_err();
+        assert_eq!(
+            (err.kind(), err.pos(), err.arg()),
+            (ErrorKind::TrailingData, 12, "4")
+        );
+        let second = Duration::from_secs(1);
+        assert_eq!(change(second, second * 2), 1.0);
+    }
+}
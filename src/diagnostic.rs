@@ -0,0 +1,265 @@
+use std::error::Error as _;
+use std::fmt::Write;
+
+use super::{ParseError, ParseErrors};
+
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+pub struct Renderer {
+    color: bool,
+    width: usize,
+    lines: usize,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Renderer {
+            color: false,
+            width: 80,
+            lines: 4,
+        }
+    }
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width.max(16);
+        self
+    }
+    pub fn lines(mut self, lines: usize) -> Self {
+        self.lines = lines.max(2);
+        self
+    }
+
+    pub fn render(&self, input: &str, err: &ParseError) -> String {
+        let mut res = String::new();
+        let mut title = err.msg().to_string();
+        if let Some(source) = err.source() {
+            title += ": ";
+            title += &source.to_string();
+        }
+        let (red, blue, bold, reset) = match self.color {
+            true => (RED, BLUE, BOLD, RESET),
+            false => ("", "", "", ""),
+        };
+        writeln!(res, "{red}error{reset}{bold}: {title}{reset}").unwrap();
+        let lines: Vec<&str> = input.split('\n').collect();
+        let first = err.line().max(1);
+        let span: Vec<&str> = err.arg().split('\n').collect();
+        let last = (first + span.len() - 1).min(lines.len());
+        let gutter = last.to_string().len();
+        writeln!(
+            res,
+            "{:gutter$}{blue}-->{reset} {}:{}",
+            "",
+            first,
+            err.pos()
+        )
+        .unwrap();
+        writeln!(res, "{:gutter$} {blue}|{reset}", "").unwrap();
+        let (head, tail) = (self.lines / 2, self.lines - self.lines / 2);
+        for n in first..=last {
+            if last - first >= self.lines && n >= first + head && n + tail <= last {
+                if n == first + head {
+                    writeln!(res, "{:gutter$} {blue}|{reset} ...", "").unwrap();
+                }
+                continue;
+            }
+            let Some(line) = lines.get(n - 1) else {
+                break;
+            };
+            let line: Vec<char> = line.trim_end_matches('\r').chars().collect();
+            let start = if n == first { err.pos().max(1) - 1 } else { 0 };
+            let len = match span.get(n - first) {
+                Some(part) if n == first && span.len() == 1 => part.chars().count().max(1),
+                Some(part) => part.chars().count(),
+                None => 0,
+            };
+            let end = if n < last {
+                line.len().max(start + len) + 1
+            } else {
+                start + len
+            };
+            let (from, to) = self.window(line.len().max(end), start, end);
+            let mut text = String::new();
+            let mut marks = String::new();
+            if from > 0 {
+                text += "...";
+                marks += "   ";
+            }
+            for i in from..to {
+                let ch = match line.get(i) {
+                    Some('\t') => ' ',
+                    Some(&ch) => ch,
+                    None => ' ',
+                };
+                text.push(ch);
+                marks.push(if (start..end).contains(&i) { '^' } else { ' ' });
+            }
+            if to < line.len() {
+                text += "...";
+            }
+            let text = text.trim_end();
+            let marks = marks.trim_end();
+            writeln!(res, "{blue}{n:>gutter$} |{reset} {text}").unwrap();
+            if !marks.is_empty() {
+                writeln!(res, "{:gutter$} {blue}|{reset} {red}{marks}{reset}", "").unwrap();
+            }
+        }
+        res
+    }
+
+    pub fn render_all(&self, input: &str, errors: &ParseErrors) -> String {
+        let mut res = String::new();
+        for (i, err) in errors.iter().enumerate() {
+            if i > 0 {
+                res.push('\n');
+            }
+            res += &self.render(input, err);
+        }
+        let (red, bold, reset) = match self.color {
+            true => (RED, BOLD, RESET),
+            false => ("", "", ""),
+        };
+        let count = errors.len();
+        if errors.is_full() && errors.limit() > 1 {
+            let msg = format!("stopped after {count} errors");
+            writeln!(res, "\n{red}error{reset}{bold}: {msg}{reset}").unwrap();
+        } else if count > 1 {
+            let msg = format!("found {count} errors");
+            writeln!(res, "\n{red}error{reset}{bold}: {msg}{reset}").unwrap();
+        }
+        res
+    }
+
+    fn window(&self, len: usize, start: usize, end: usize) -> (usize, usize) {
+        let width = self.width;
+        if len <= width {
+            return (0, len);
+        }
+        let end = end.min(start + width / 2).max(start + 1);
+        let from = (start + end).saturating_sub(width) / 2;
+        let from = from.min(len - width);
+        (from, from + width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find;
+
+    fn render(year: u16, day: u8, input: &str, renderer: &Renderer) -> String {
+        match find(year, day).unwrap().parse(input) {
+            Err(err) => renderer.render(input, &err),
+            Ok(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn caret() {
+        let text = render(2015, 1, "(()x)", &Renderer::new());
+        assert_eq!(
+            text,
+            "error: Invalid character\n --> 1:4\n  |\n1 | (()x)\n  |    ^\n"
+        );
+        let text = render(2015, 2, "1x2x3\n4xZZx6\n", &Renderer::new());
+        assert_eq!(
+            text,
+            "error: Invalid number: invalid digit found in string\n --> 2:3\n  |\n2 | 4xZZx6\n  |   ^^\n"
+        );
+        let text = render(2015, 2, "1x2x", &Renderer::new());
+        assert!(text.ends_with("1 | 1x2x\n  |     ^\n"));
+        let text = render(2015, 3, "\u{a0}\u{a0}x", &Renderer::new());
+        assert!(text.ends_with(" --> 1:1\n  |\n1 | \u{a0}\u{a0}x\n  | ^\n"));
+    }
+
+    #[test]
+    fn render_all() {
+        let input = "1x2x3\n4xZx6\n7x8\n9x9x9";
+        let render = |limit| match find(2015, 2).unwrap().parse_all(input, limit) {
+            Err(errors) => Renderer::new().render_all(input, &errors),
+            Ok(_) => panic!(),
+        };
+        let text = render(10);
+        assert_eq!(text.matches("error: ").count(), 3);
+        assert!(text.contains(" --> 2:3\n"));
+        assert!(text.contains(" --> 3:4\n"));
+        assert!(text.ends_with("\nerror: found 2 errors\n"));
+        let text = render(1);
+        assert!(!text.contains(" --> 3:4\n"));
+        assert!(!text.contains("error: found"));
+        let text = render(2);
+        assert!(text.ends_with("\nerror: stopped after 2 errors\n"));
+    }
+
+    #[test]
+    fn long_line() {
+        let mut input = "(".repeat(8122);
+        input += "x";
+        input += &")".repeat(1000);
+        let text = render(2015, 1, &input, &Renderer::new().width(20));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], " --> 1:8123");
+        assert_eq!(
+            lines[3],
+            format!("1 | ...{}x{}...", "(".repeat(10), ")".repeat(9))
+        );
+        assert_eq!(lines[4], format!("  | {}^", " ".repeat(13)));
+    }
+
+    #[test]
+    fn multi_line() {
+        let input = "abc\ndefghij\nklmnopqrstuvwxyz";
+        let text = render(2015, 4, input, &Renderer::new());
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[3..],
+            [
+                "1 | abc",
+                "  | ^^^^",
+                "2 | defghij",
+                "  | ^^^^^^^^",
+                "3 | klmnopqrstuvwxyz",
+                "  | ^^^^^^^^^^^^^^^^"
+            ]
+        );
+        let input = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\no";
+        let text = render(2015, 4, input, &Renderer::new());
+        assert!(text.contains(" 2 | b\n   | ^^\n   | ...\n14 | n\n"));
+    }
+
+    #[test]
+    fn newline() {
+        let input = "())\n";
+        let strict = crate::ParseOptions::new().normalize(crate::Normalize::NONE);
+        let errors = find(2015, 1)
+            .unwrap()
+            .parse_with(input, &strict)
+            .err()
+            .unwrap();
+        let err = errors.iter().next().unwrap();
+        let text = Renderer::new().render(input, err);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[3..5], ["1 | ())", "  |    ^"]);
+        let text = render(2015, 3, "^>\n\n", &Renderer::new());
+        assert!(text.contains("1 | ^>\n  |   ^\n"), "{text}");
+    }
+
+    #[test]
+    fn color() {
+        let text = render(2015, 1, "x", &Renderer::new().color(true));
+        assert!(text.starts_with("\x1b[1;31merror\x1b[0m"));
+        assert!(text.contains("\x1b[1;31m^\x1b[0m"));
+    }
+}
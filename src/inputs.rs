@@ -0,0 +1,81 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+
+pub struct InputDir {
+    root: PathBuf,
+}
+
+impl InputDir {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        InputDir { root: root.into() }
+    }
+    pub fn from_env() -> Self {
+        match env::var_os(INPUT_DIR_VAR) {
+            Some(root) if !root.is_empty() => Self::new(root),
+            _ => Self::new(DEFAULT_INPUT_DIR),
+        }
+    }
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("{day:02}.txt"))
+    }
+    pub fn open(&self, year: u16, day: u8) -> io::Result<Option<File>> {
+        let path = self.path(year, day);
+        match File::open(&path) {
+            Ok(file) => Ok(Some(file)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(with_path(&path, err)),
+        }
+    }
+    pub fn read(&self, year: u16, day: u8) -> io::Result<Option<String>> {
+        let path = self.path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(input) => Ok(Some(input)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(with_path(&path, err)),
+        }
+    }
+}
+
+fn with_path(path: &Path, err: io::Error) -> io::Error {
+    io::Error::new(err.kind(), format!("{}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path() {
+        let dir = InputDir::new("inputs");
+        let path = Path::new("inputs").join("2015").join("04.txt");
+        assert_eq!(dir.path(2015, 4), path);
+        let path = Path::new("inputs").join("2015").join("25.txt");
+        assert_eq!(dir.path(2015, 25), path);
+    }
+
+    #[test]
+    fn missing() {
+        let dir = InputDir::new(env::temp_dir().join("aoc-solver-missing-inputs"));
+        assert!(dir.read(2015, 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn error_path() {
+        let root = env::temp_dir().join(format!("aoc-solver-bad-inputs-{}", std::process::id()));
+        let dir = InputDir::new(&root);
+        fs::create_dir_all(dir.path(2015, 1).join("not-a-file")).unwrap();
+        let err = dir.read(2015, 1).unwrap_err();
+        fs::remove_dir_all(&root).unwrap();
+        let path = dir.path(2015, 1).display().to_string();
+        assert!(err.to_string().starts_with(&path), "{err}");
+    }
+}
@@ -1,11 +1,142 @@
-use std::{error, fmt};
+use std::borrow::Cow;
+use std::io::BufRead;
+use std::ops::{Range, RangeInclusive};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{error, fmt, io};
+
+pub use answer::Answer;
+
+mod answer;
+pub mod answers;
+pub mod bench;
+pub mod diagnostic;
+pub mod inputs;
+pub mod output;
+pub mod runner;
 
 pub struct Problem {
     year: u16,
     day: u8,
     title: &'static str,
     parts: u8,
-    parse: for<'a> fn(&'a str) -> Result<Box<dyn Solver>, ParseError<'a>>,
+    normalize: Normalize,
+    parse: ParseFn,
+    stream: Option<StreamFn>,
+    params: &'static [Param],
+    algos: &'static [Algo],
+}
+
+type ParseFn = for<'a> fn(&'a str, &mut ParseErrors<'a>) -> Option<Box<dyn Solver>>;
+
+type StreamFn = fn(
+    &mut dyn BufRead,
+    &mut ParseErrors<'static>,
+    Normalize,
+) -> io::Result<Option<Box<dyn Solver>>>;
+
+#[derive(Clone, Copy)]
+pub struct Algo {
+    name: &'static str,
+    parse: ParseFn,
+}
+
+impl Algo {
+    pub(crate) const fn new(name: &'static str, parse: ParseFn) -> Self {
+        Algo { name, parse }
+    }
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl fmt::Display for Algo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl fmt::Debug for Algo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Algo").field(&self.name).finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Param {
+    name: &'static str,
+    default: u64,
+    min: u64,
+    max: u64,
+}
+
+impl Param {
+    pub(crate) const fn new(name: &'static str, default: u64, min: u64, max: u64) -> Self {
+        Param {
+            name,
+            default,
+            min,
+            max,
+        }
+    }
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    pub fn default(&self) -> u64 {
+        self.default
+    }
+    pub fn range(&self) -> RangeInclusive<u64> {
+        self.min..=self.max
+    }
+    pub fn value(&self, ctx: &SolveContext) -> Result<u64, SolveError> {
+        match ctx.params.get(self.name).unwrap_or(self.default) {
+            value if self.range().contains(&value) => Ok(value),
+            _ => Err(SolveError::InvalidInput {
+                reason: "Parameter out of range",
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}={} ({}..={})",
+            self.name, self.default, self.min, self.max
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    values: Vec<(&'static str, u64)>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.values
+            .iter()
+            .find(|&&(key, _)| key == name)
+            .map(|&(_, value)| value)
+    }
+    pub fn set(&mut self, name: &'static str, value: u64) {
+        match self.values.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, old)) => *old = value,
+            None => self.values.push((name, value)),
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        self.values.iter().copied()
+    }
 }
 
 impl Problem {
@@ -21,14 +152,148 @@ impl Problem {
     pub fn parts(&self) -> u8 {
         self.parts
     }
+    pub fn normalize(&self) -> Normalize {
+        self.normalize
+    }
+    pub fn params(&self) -> &'static [Param] {
+        self.params
+    }
+    pub fn algos(&self) -> &'static [Algo] {
+        self.algos
+    }
+    pub fn algo(&self, name: &str) -> Option<&'static Algo> {
+        self.algos.iter().find(|algo| algo.name == name)
+    }
+    pub fn default_algo(&self) -> Option<&'static Algo> {
+        self.algos.first()
+    }
+    pub fn parse_param<'a>(&self, arg: &'a str) -> Result<(&'static str, u64), ParseError<'a>> {
+        let error = |kind, msg, part: &str| {
+            let start = offset(arg, part);
+            ParseError::new(kind, arg, start..start + part.len()).with_msg(msg)
+        };
+        let Some((name, value)) = arg.split_once('=') else {
+            let end = &arg[arg.len()..];
+            return Err(error(ErrorKind::UnexpectedEnd, "Expected NAME=VALUE", end));
+        };
+        let Some(param) = self.params.iter().find(|param| param.name == name) else {
+            return Err(error(
+                ErrorKind::InvalidCharacter,
+                "Unknown parameter",
+                name,
+            ));
+        };
+        match value.parse() {
+            Ok(value) if param.range().contains(&value) => Ok((param.name, value)),
+            Ok(_) => Err(error(ErrorKind::OutOfRange, "Invalid value", value)),
+            Err(err) => {
+                Err(error(ErrorKind::NumberFormat, "Invalid value", value).with_source(err))
+            }
+        }
+    }
+    pub fn id(&self) -> ProblemId {
+        ProblemId {
+            year: self.year,
+            day: self.day,
+        }
+    }
     pub fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Solver>, ParseError<'a>> {
-        (self.parse)(input)
+        self.parse_with(input, &ParseOptions::new())
+            .map_err(ParseErrors::into_first)
+    }
+    pub fn parse_all<'a>(
+        &self,
+        input: &'a str,
+        limit: usize,
+    ) -> Result<Box<dyn Solver>, ParseErrors<'a>> {
+        self.parse_with(input, &ParseOptions::new().max_errors(limit))
+    }
+    pub fn parse_with<'a>(
+        &self,
+        input: &'a str,
+        options: &ParseOptions,
+    ) -> Result<Box<dyn Solver>, ParseErrors<'a>> {
+        let normalize = options.normalize.unwrap_or(self.normalize);
+        let mut errors = ParseErrors::new(options.max_errors);
+        let parse = match self.algo_parse(options) {
+            Ok(parse) => parse,
+            Err(err) => {
+                errors.push(err);
+                return Err(errors);
+            }
+        };
+        let text = normalize.apply(input);
+        let solver = if text == input {
+            parse(input, &mut errors)
+        } else {
+            let mut found = ParseErrors::new(options.max_errors);
+            let solver = parse(&text, &mut found);
+            for err in found {
+                let span = err.span();
+                let start = normalize.origin(input, span.start);
+                let end = normalize.origin(input, span.end).max(start);
+                errors.push(err.relocate(input, start..end));
+            }
+            solver
+        };
+        errors.finish(input, solver)
+    }
+    pub fn validate<'a>(&self, input: &'a str) -> Result<InputSummary, ParseError<'a>> {
+        self.validate_with(input, &ParseOptions::new())
+            .map_err(ParseErrors::into_first)
+    }
+    pub fn validate_with<'a>(
+        &self,
+        input: &'a str,
+        options: &ParseOptions,
+    ) -> Result<InputSummary, ParseErrors<'a>> {
+        let solver = self.parse_with(input, options)?;
+        let normalize = options.normalize.unwrap_or(self.normalize);
+        Ok(InputSummary {
+            lines: normalize.apply(input).lines().count(),
+            ..solver.summary()
+        })
     }
-    pub fn solve_all<'a>(&self, input: &'a str) -> Result<Vec<String>, ParseError<'a>> {
-        let mut res = Vec::<String>::new();
+    pub fn parse_reader(
+        &self,
+        mut reader: impl BufRead,
+        options: &ParseOptions,
+    ) -> io::Result<Result<Box<dyn Solver>, ParseErrors<'static>>> {
+        let (Some(stream), None) = (self.stream, options.algo) else {
+            let mut input = String::new();
+            reader.read_to_string(&mut input)?;
+            return Ok(self
+                .parse_with(&input, options)
+                .map_err(ParseErrors::into_owned));
+        };
+        let normalize = options.normalize.unwrap_or(self.normalize);
+        let mut errors = ParseErrors::new(options.max_errors);
+        let solver = stream(&mut reader, &mut errors, normalize)?;
+        Ok(errors.finish("", solver))
+    }
+    fn algo_parse(&self, options: &ParseOptions) -> Result<ParseFn, ParseError<'static>> {
+        let Some(name) = options.algo else {
+            return Ok(self.parse);
+        };
+        match self.algo(name) {
+            Some(algo) => Ok(algo.parse),
+            None => Err(
+                ParseError::new(ErrorKind::InvalidCharacter, name, 0..name.len())
+                    .with_msg("Unknown algorithm"),
+            ),
+        }
+    }
+    pub fn solve_part(&self, input: &str, part: u8) -> Result<Answer, Error> {
+        if part == 0 || part > self.parts {
+            return Err(Error::Solve(SolveError::PartNotFound(part)));
+        }
+        Ok(self.parse(input)?.solve(part)?)
+    }
+    pub fn solve_all(&self, input: &str) -> Result<Vec<Answer>, Error> {
+        let mut res = Vec::<Answer>::new();
         let mut solver = self.parse(input)?;
         for part in 1..=self.parts {
-            res.push(solver.solve(part).unwrap());
+            res.push(solver.solve(part)?);
         }
         Ok(res)
     }
@@ -40,26 +305,302 @@ impl fmt::Display for Problem {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ProblemId {
+    year: u16,
+    day: u8,
+}
+
+impl ProblemId {
+    pub fn new(year: u16, day: u8) -> Option<Self> {
+        if year >= 2015 && (1..=25).contains(&day) {
+            Some(ProblemId { year, day })
+        } else {
+            None
+        }
+    }
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+    pub fn problem(&self) -> Option<&'static Problem> {
+        find(self.year, self.day)
+    }
+}
+
+impl fmt::Display for ProblemId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+impl FromStr for ProblemId {
+    type Err = ParseError<'static>;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = |kind, msg, part: &str| {
+            let start = offset(text, part);
+            ParseError::new(kind, text, start..start + part.len())
+                .with_msg(msg)
+                .into_owned()
+        };
+        let Some((year, day)) = text.split_once(['/', '-']) else {
+            let end = &text[text.len()..];
+            return Err(error(ErrorKind::UnexpectedEnd, "Expected YEAR/DAY", end));
+        };
+        let parsed = match year.parse() {
+            Ok(year) if year >= 2015 => year,
+            Ok(_) => return Err(error(ErrorKind::OutOfRange, "Invalid year", year)),
+            Err(_) => return Err(error(ErrorKind::NumberFormat, "Invalid year", year)),
+        };
+        match day.parse() {
+            Ok(day @ 1..=25) => Ok(ProblemId { year: parsed, day }),
+            Ok(_) => Err(error(ErrorKind::OutOfRange, "Invalid day", day)),
+            Err(_) => Err(error(ErrorKind::NumberFormat, "Invalid day", day)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorKind {
+    InvalidCharacter,
+    UnexpectedEnd,
+    TrailingData,
+    NumberFormat,
+    OutOfRange,
+    TooLong,
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::InvalidCharacter => "Invalid character",
+            ErrorKind::UnexpectedEnd => "Expected data",
+            ErrorKind::TrailingData => "Unexpected data",
+            ErrorKind::NumberFormat => "Invalid number",
+            ErrorKind::OutOfRange => "Out of range",
+            ErrorKind::TooLong => "Too long",
+        }
+    }
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::InvalidCharacter => "InvalidCharacter",
+            ErrorKind::UnexpectedEnd => "UnexpectedEnd",
+            ErrorKind::TrailingData => "TrailingData",
+            ErrorKind::NumberFormat => "NumberFormat",
+            ErrorKind::OutOfRange => "OutOfRange",
+            ErrorKind::TooLong => "TooLong",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Normalize {
+    bom: bool,
+    crlf: bool,
+    newline: bool,
+}
+
+impl Normalize {
+    pub const NONE: Normalize = Normalize {
+        bom: false,
+        crlf: false,
+        newline: false,
+    };
+    pub const ALL: Normalize = Normalize {
+        bom: true,
+        crlf: true,
+        newline: true,
+    };
+
+    pub const fn bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+    pub const fn crlf(mut self, crlf: bool) -> Self {
+        self.crlf = crlf;
+        self
+    }
+    pub const fn newline(mut self, newline: bool) -> Self {
+        self.newline = newline;
+        self
+    }
+    pub fn apply<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(&input[self.skip(input)..]);
+        if self.crlf && text.contains("\r\n") {
+            text = Cow::Owned(text.replace("\r\n", "\n"));
+        }
+        if self.newline && text.ends_with('\n') {
+            match &mut text {
+                Cow::Borrowed(text) => *text = &text[..text.len() - 1],
+                Cow::Owned(text) => drop(text.pop()),
+            }
+        }
+        text
+    }
+
+    fn skip(&self, input: &str) -> usize {
+        match self.bom && input.starts_with(BOM) {
+            true => BOM.len_utf8(),
+            false => 0,
+        }
+    }
+    fn origin(&self, input: &str, offset: usize) -> usize {
+        let start = self.skip(input);
+        if !self.crlf {
+            return start + offset;
+        }
+        let bytes = input.as_bytes();
+        let (mut i, mut kept) = (start, 0);
+        while kept < offset && i < bytes.len() {
+            if !bytes[i..].starts_with(b"\r\n") {
+                kept += 1;
+            }
+            i += 1;
+        }
+        i
+    }
+}
+
+const BOM: char = '\u{feff}';
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseOptions {
+    max_errors: usize,
+    normalize: Option<Normalize>,
+    algo: Option<&'static str>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        ParseOptions {
+            max_errors: 1,
+            normalize: None,
+            algo: None,
+        }
+    }
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = max_errors;
+        self
+    }
+    pub fn normalize(mut self, normalize: Normalize) -> Self {
+        self.normalize = Some(normalize);
+        self
+    }
+    pub fn algo(mut self, name: &'static str) -> Self {
+        self.algo = Some(name);
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct ParseError<'a> {
+    kind: ErrorKind,
     msg: &'static str,
     line: usize,
     pos: usize,
-    source: Option<Box<dyn error::Error + 'static>>,
-    arg: &'a str,
+    span: Range<usize>,
+    source: Option<Box<dyn error::Error + Send + Sync + 'static>>,
+    arg: Cow<'a, str>,
+}
+
+impl<'a> ParseError<'a> {
+    pub(crate) fn new(kind: ErrorKind, input: &'a str, span: Range<usize>) -> Self {
+        let before = &input[..span.start];
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            kind,
+            msg: "",
+            line: before.matches('\n').count() + 1,
+            pos: before[start..].chars().count() + 1,
+            arg: Cow::Borrowed(&input[span.clone()]),
+            span,
+            source: None,
+        }
+    }
+    pub(crate) fn with_msg(mut self, msg: &'static str) -> Self {
+        self.msg = msg;
+        self
+    }
+    fn shift(mut self, line: usize, offset: usize) -> Self {
+        self.line += line;
+        self.span = self.span.start + offset..self.span.end + offset;
+        self
+    }
+    fn relocate<'b>(self, input: &'b str, span: Range<usize>) -> ParseError<'b> {
+        let mut err = ParseError::new(self.kind, input, span).with_msg(self.msg);
+        err.source = self.source;
+        err
+    }
+    pub(crate) fn with_source<E>(mut self, source: E) -> Self
+    where
+        E: error::Error + Send + Sync + 'static,
+    {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+    pub fn msg(&self) -> &'static str {
+        match self.msg {
+            "" => self.kind.as_str(),
+            msg => msg,
+        }
+    }
+    pub fn line(&self) -> usize {
+        self.line
+    }
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+    pub fn arg(&self) -> &str {
+        &self.arg
+    }
+    pub fn into_owned(self) -> ParseError<'static> {
+        ParseError {
+            kind: self.kind,
+            msg: self.msg,
+            line: self.line,
+            pos: self.pos,
+            span: self.span,
+            source: self.source,
+            arg: Cow::Owned(self.arg.into_owned()),
+        }
+    }
 }
 
 impl<'a> PartialEq for ParseError<'a> {
     fn eq(&self, rhs: &Self) -> bool {
-        let left = (self.msg, self.line, self.pos, self.arg);
-        let right = (rhs.msg, rhs.line, rhs.pos, rhs.arg);
+        let left = (
+            self.kind, self.msg, self.line, self.pos, &self.span, &self.arg,
+        );
+        let right = (rhs.kind, rhs.msg, rhs.line, rhs.pos, &rhs.span, &rhs.arg);
         left == right
     }
 }
 
 impl<'a> fmt::Display for ParseError<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{} {}", self.line, self.pos, self.msg)?;
+        write!(f, "{}:{} {}", self.line, self.pos, self.msg())?;
         if let Some(inner) = &self.source {
             write!(f, ": {}", inner)?;
         }
@@ -76,10 +617,113 @@ impl<'a> error::Error for ParseError<'a> {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub struct ParseErrors<'a> {
+    errors: Vec<ParseError<'a>>,
+    limit: usize,
+}
+
+impl<'a> ParseErrors<'a> {
+    pub(crate) fn new(limit: usize) -> Self {
+        ParseErrors {
+            errors: Vec::new(),
+            limit: limit.max(1),
+        }
+    }
+    pub fn push(&mut self, err: ParseError<'a>) -> bool {
+        if !self.is_full() {
+            self.errors.push(err);
+        }
+        !self.is_full()
+    }
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+    pub fn is_full(&self) -> bool {
+        self.errors.len() >= self.limit
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, ParseError<'a>> {
+        self.errors.iter()
+    }
+    fn finish(
+        mut self,
+        input: &'a str,
+        solver: Option<Box<dyn Solver>>,
+    ) -> Result<Box<dyn Solver>, Self> {
+        match solver {
+            Some(solver) if self.is_empty() => Ok(solver),
+            _ => {
+                if self.is_empty() {
+                    self.push(ParseError::new(ErrorKind::InvalidCharacter, input, 0..0));
+                }
+                Err(self)
+            }
+        }
+    }
+    fn into_first(self) -> ParseError<'a> {
+        // ParseErrors only reaches callers through parse_with() and parse_reader(),
+        // which never return an empty set.
+        self.errors.into_iter().next().unwrap()
+    }
+    pub fn into_owned(self) -> ParseErrors<'static> {
+        ParseErrors {
+            errors: self
+                .errors
+                .into_iter()
+                .map(ParseError::into_owned)
+                .collect(),
+            limit: self.limit,
+        }
+    }
+}
+
+impl<'a> IntoIterator for ParseErrors<'a> {
+    type Item = ParseError<'a>;
+    type IntoIter = std::vec::IntoIter<ParseError<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b ParseErrors<'a> {
+    type Item = &'b ParseError<'a>;
+    type IntoIter = std::slice::Iter<'b, ParseError<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.iter()
+    }
+}
+
+impl<'a> fmt::Display for ParseErrors<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, err) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{err}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> error::Error for ParseErrors<'a> {}
+
 #[derive(Debug, PartialEq)]
 pub enum SolveError {
     NotImplemented,
     PartNotFound(u8),
+    NoSolution,
+    Overflow,
+    Timeout,
+    Cancelled,
+    InvalidInput { reason: &'static str },
 }
 
 impl fmt::Display for SolveError {
@@ -87,6 +731,11 @@ impl fmt::Display for SolveError {
         match self {
             SolveError::NotImplemented => write!(f, "Not implemented")?,
             SolveError::PartNotFound(part) => write!(f, "Part#{part} not found")?,
+            SolveError::NoSolution => write!(f, "No solution")?,
+            SolveError::Overflow => write!(f, "Overflow")?,
+            SolveError::Timeout => write!(f, "Timeout")?,
+            SolveError::Cancelled => write!(f, "Cancelled")?,
+            SolveError::InvalidInput { reason } => write!(f, "Invalid input: {reason}")?,
         }
         Ok(())
     }
@@ -94,8 +743,336 @@ impl fmt::Display for SolveError {
 
 impl error::Error for SolveError {}
 
-pub trait Solver {
-    fn solve(&mut self, part: u8) -> Result<String, SolveError>;
+type ProgressFn = dyn Fn(&Progress) + Send + Sync;
+
+#[derive(Clone, Default)]
+pub struct SolveContext {
+    cancelled: Arc<AtomicBool>,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    started: Option<Instant>,
+    progress: Option<Arc<ProgressFn>>,
+    params: Params,
+}
+
+impl fmt::Debug for SolveContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SolveContext")
+            .field("cancelled", &self.is_cancelled())
+            .field("timeout", &self.timeout)
+            .field("deadline", &self.deadline)
+            .field("progress", &self.progress.is_some())
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+impl SolveContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self.started.get_or_insert_with(Instant::now);
+        self
+    }
+
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn progress(mut self, progress: impl Fn(&Progress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    pub fn params(mut self, params: Params) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn start(&self) -> Self {
+        SolveContext {
+            cancelled: self.cancelled.clone(),
+            timeout: self.timeout,
+            deadline: self.deadline,
+            started: Some(Instant::now()),
+            progress: self.progress.clone(),
+            params: self.params.clone(),
+        }
+    }
+
+    pub fn report(&self, done: u64, total: Option<u64>) {
+        if let Some(progress) = &self.progress {
+            let elapsed = self
+                .started
+                .map_or(Duration::ZERO, |started| started.elapsed());
+            progress(&Progress {
+                done,
+                total,
+                elapsed,
+            });
+        }
+    }
+
+    pub fn check(&self) -> Result<(), SolveError> {
+        if self.is_cancelled() {
+            return Err(SolveError::Cancelled);
+        }
+        let timeout = self
+            .started
+            .zip(self.timeout)
+            .map(|(start, limit)| start + limit);
+        let deadline = match (self.deadline, timeout) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        match deadline {
+            Some(deadline) if Instant::now() >= deadline => Err(SolveError::Timeout),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError<'static>),
+    Solve(SolveError),
+    Io(io::Error),
+}
+
+impl PartialEq for Error {
+    fn eq(&self, rhs: &Self) -> bool {
+        match (self, rhs) {
+            (Error::Parse(left), Error::Parse(right)) => left == right,
+            (Error::Solve(left), Error::Solve(right)) => left == right,
+            (Error::Io(left), Error::Io(right)) => left.kind() == right.kind(),
+            _ => false,
+        }
+    }
+}
+
+impl<'a> From<ParseErrors<'a>> for Error {
+    fn from(errors: ParseErrors<'a>) -> Self {
+        Error::Parse(errors.into_first().into_owned())
+    }
+}
+
+impl<'a> From<ParseError<'a>> for Error {
+    fn from(err: ParseError<'a>) -> Self {
+        Error::Parse(err.into_owned())
+    }
+}
+
+impl From<SolveError> for Error {
+    fn from(err: SolveError) -> Self {
+        Error::Solve(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(_) => write!(f, "Parse error"),
+            Error::Solve(_) => write!(f, "Solve error"),
+            Error::Io(_) => write!(f, "I/O error"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Parse(err) => Some(err),
+            Error::Solve(err) => Some(err),
+            Error::Io(err) => Some(err),
+        }
+    }
+}
+
+pub(crate) fn offset(input: &str, part: &str) -> usize {
+    part.as_ptr() as usize - input.as_ptr() as usize
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    done: u64,
+    total: Option<u64>,
+    elapsed: Duration,
+}
+
+impl Progress {
+    pub fn done(&self) -> u64 {
+        self.done
+    }
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+    pub fn rate(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            0.0 => 0.0,
+            secs => self.done as f64 / secs,
+        }
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} iterations, {:.0}/s", self.done, self.rate())?;
+        if let Some(total) = self.total.filter(|&total| total > 0) {
+            let percent = 100.0 * self.done as f64 / total as f64;
+            write!(f, ", {percent:.0}% of ~{total}")?;
+        }
+        Ok(())
+    }
+}
+
+pub trait Solver: Send {
+    fn solve(&mut self, part: u8) -> Result<Answer, SolveError>;
+
+    fn solve_with(&mut self, part: u8, ctx: &SolveContext) -> Result<Answer, SolveError> {
+        ctx.check()?;
+        self.solve(part)
+    }
+    fn summary(&self) -> InputSummary {
+        InputSummary::default()
+    }
+    fn into_shared(self: Box<Self>) -> Result<Arc<dyn SharedSolver>, Box<dyn Solver>>;
+}
+
+pub trait SharedSolver: Send + Sync {
+    fn solve(&self, part: u8) -> Result<Answer, SolveError>;
+
+    fn solve_with(&self, part: u8, ctx: &SolveContext) -> Result<Answer, SolveError> {
+        ctx.check()?;
+        self.solve(part)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputSummary {
+    lines: usize,
+    items: usize,
+    range: Option<(u64, u64)>,
+}
+
+impl InputSummary {
+    pub fn new(items: usize) -> Self {
+        InputSummary {
+            items,
+            ..Self::default()
+        }
+    }
+    pub fn with_range(mut self, min: u64, max: u64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+    pub fn items(&self) -> usize {
+        self.items
+    }
+    pub fn range(&self) -> Option<(u64, u64)> {
+        self.range
+    }
+}
+
+impl fmt::Display for InputSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} lines, {} items", self.lines, self.items)?;
+        if let Some((min, max)) = self.range {
+            write!(f, ", values {min}..={max}")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) trait LineParser: Solver + Default + 'static {
+    fn line<'a>(&mut self, line: &'a str) -> Result<(), ParseError<'a>>;
+}
+
+pub(crate) fn parse_lines<'a, P: LineParser>(
+    input: &'a str,
+    errors: &mut ParseErrors<'a>,
+) -> Option<Box<dyn Solver>> {
+    let mut res = P::default();
+    for (i, line) in input.split_terminator('\n').enumerate() {
+        if let Err(err) = res.line(line) {
+            if !errors.push(err.shift(i, offset(input, line))) {
+                return None;
+            }
+        }
+    }
+    errors.is_empty().then(|| Box::new(res) as Box<dyn Solver>)
+}
+
+pub(crate) fn stream_lines<P: LineParser>(
+    reader: &mut dyn BufRead,
+    errors: &mut ParseErrors<'static>,
+    normalize: Normalize,
+) -> io::Result<Option<Box<dyn Solver>>> {
+    let mut res = P::default();
+    let mut buf = String::new();
+    let (mut i, mut start) = (0, 0);
+    let mut blank = None;
+    loop {
+        buf.clear();
+        let len = reader.read_line(&mut buf)?;
+        if len == 0 {
+            break;
+        }
+        if let Some((i, start)) = blank.take() {
+            if let Err(err) = res.line("") {
+                if !errors.push(err.shift(i, start)) {
+                    return Ok(None);
+                }
+            }
+        }
+        if normalize.newline && (buf == "\n" || normalize.crlf && buf == "\r\n") {
+            blank = Some((i, start));
+            (i, start) = (i + 1, start + len);
+            continue;
+        }
+        let mut line = buf.as_str();
+        if let Some(rest) = line.strip_suffix('\n') {
+            line = match normalize.crlf {
+                true => rest.strip_suffix('\r').unwrap_or(rest),
+                false => rest,
+            };
+        }
+        let skip = if i == 0 { normalize.skip(line) } else { 0 };
+        if let Err(err) = res.line(&line[skip..]) {
+            let mut err = err.shift(i, start + skip).into_owned();
+            if skip > 0 {
+                err.pos += 1;
+            }
+            if !errors.push(err) {
+                return Ok(None);
+            }
+        }
+        (i, start) = (i + 1, start + len);
+    }
+    Ok(errors.is_empty().then(|| Box::new(res) as Box<dyn Solver>))
 }
 
 mod y2015 {
@@ -114,13 +1091,39 @@ pub const PROBLEMS: [Problem; 5] = [
     y2015::d5::PROBLEM,
 ];
 
+pub fn find(year: u16, day: u8) -> Option<&'static Problem> {
+    let problems = by_year(year);
+    let i = problems.binary_search_by_key(&day, Problem::day).ok()?;
+    Some(&problems[i])
+}
+
+pub fn by_year(year: u16) -> &'static [Problem] {
+    let problems: &'static [Problem] = &PROBLEMS;
+    let start = problems.partition_point(|problem| problem.year < year);
+    let end = problems.partition_point(|problem| problem.year <= year);
+    &problems[start..end]
+}
+
+pub fn years() -> impl Iterator<Item = u16> {
+    let mut prev = 0;
+    PROBLEMS.iter().filter_map(move |problem| {
+        let year = problem.year;
+        (year != prev).then(|| {
+            prev = year;
+            year
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn problems_order() {
         let mut year: u16 = 0;
         let mut day: u8 = 0;
-        for problem in super::PROBLEMS {
+        for problem in PROBLEMS {
             if year < problem.year() {
                 year = problem.year();
                 day = 0;
@@ -128,9 +1131,335 @@ mod tests {
             assert!(year == problem.year());
             assert!(day < problem.day());
             day = problem.day();
-            assert!(year >= 2015 && year < 2024);
-            assert!(day >= 1 && day <= 25);
+            assert!((2015..2024).contains(&year));
+            assert!((1..=25).contains(&day));
             assert_eq!(problem.parts(), 2)
         }
     }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(find(2015, 4).unwrap().title(), "The Ideal Stocking Stuffer");
+        assert!(find(2015, 26).is_none());
+        assert!(find(2014, 1).is_none());
+        assert_eq!(by_year(2015).len(), 5);
+        assert!(by_year(2016).is_empty());
+        assert_eq!(years().collect::<Vec<_>>(), [2015]);
+        assert_eq!(
+            find(2015, 1).unwrap().id(),
+            ProblemId::new(2015, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn problem_id() {
+        let id = ProblemId::new(2015, 4).unwrap();
+        assert_eq!("2015/4".parse(), Ok(id));
+        assert_eq!("2015-04".parse(), Ok(id));
+        assert_eq!(id.to_string(), "2015/4");
+        assert_eq!(id.problem().unwrap().day(), 4);
+        for text in ["2015", "2015/0", "2015/26", "2014/1", "x/1", "2015/4/1"] {
+            assert!(text.parse::<ProblemId>().is_err());
+        }
+        let err = "2015/x".parse::<ProblemId>().unwrap_err();
+        assert_eq!(
+            (err.kind(), err.msg(), err.pos()),
+            (ErrorKind::NumberFormat, "Invalid day", 6)
+        );
+        let err = "2015".parse::<ProblemId>().unwrap_err();
+        assert_eq!((err.kind(), err.pos()), (ErrorKind::UnexpectedEnd, 5));
+        let err = "2014/1".parse::<ProblemId>().unwrap_err();
+        assert_eq!((err.kind(), err.pos()), (ErrorKind::OutOfRange, 1));
+    }
+
+    #[test]
+    fn solve_all() {
+        let problem = find(2015, 1).unwrap();
+        let answers = problem.solve_all("())").unwrap();
+        assert_eq!(answers, [Answer::from(-1i64), Answer::from(3u64)]);
+        assert!(matches!(problem.solve_all("(x"), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn error_source() {
+        use std::error::Error as _;
+        fn check<T: error::Error + Send + Sync + 'static>(_: &T) {}
+        let input = String::from("1x2xZ");
+        let err = find(2015, 2).unwrap().solve_all(&input).unwrap_err();
+        drop(input);
+        check(&err);
+        assert_eq!(err.to_string(), "Parse error");
+        let source = err.source().unwrap();
+        let parse = source.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((parse.line(), parse.pos(), parse.arg()), (1, 5, "Z"));
+        let source = source.source().unwrap();
+        assert!(source.downcast_ref::<std::num::ParseIntError>().is_some());
+        let err = Error::from(SolveError::PartNotFound(3));
+        assert_eq!(err.to_string(), "Solve error");
+        let source = err.source().unwrap();
+        assert_eq!(source.downcast_ref(), Some(&SolveError::PartNotFound(3)));
+        assert!(source.source().is_none());
+        let err = Error::from(io::Error::other(SolveError::NotImplemented));
+        assert_eq!(err.to_string(), "I/O error");
+        let source = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(source.to_string(), "Not implemented");
+        let inner = source.get_ref().unwrap().downcast_ref();
+        assert_eq!(inner, Some(&SolveError::NotImplemented));
+    }
+
+    #[test]
+    fn owned_parse_error() {
+        fn check<T: Send + Sync + 'static>(_: &T) {}
+        let input = String::from("1x2xZ");
+        let err = match find(2015, 2).unwrap().parse(&input) {
+            Err(err) => err.into_owned(),
+            Ok(_) => panic!(),
+        };
+        drop(input);
+        check(&err);
+        assert_eq!((err.line(), err.pos(), err.arg()), (1, 5, "Z"));
+        assert_eq!(err.kind(), ErrorKind::NumberFormat);
+        let err = std::thread::spawn(move || Error::from(err)).join().unwrap();
+        let Error::Parse(err) = err else { panic!() };
+        let err: Box<dyn error::Error + Send + Sync> = Box::new(err);
+        assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+        let err = "2015/x".parse::<ProblemId>().unwrap_err();
+        assert_eq!((err.span(), err.arg()), (5..6, "x"));
+    }
+
+    #[test]
+    fn span() {
+        let parse = |day, input| match find(2015, day).unwrap().parse(input) {
+            Err(err) => (err.line(), err.pos(), err.span(), err.arg().to_string()),
+            Ok(_) => panic!(),
+        };
+        assert_eq!(parse(1, "(\u{feff})"), (1, 2, 1..4, "\u{feff}".into()));
+        assert_eq!(parse(3, "^\u{a0}>"), (1, 2, 1..3, "\u{a0}".into()));
+        assert_eq!(parse(2, "1x2x3\n1é\n"), (2, 1, 6..9, "1é".into()));
+        assert_eq!(parse(2, "1x2x3\r\n4é\n"), (2, 1, 7..10, "4é".into()));
+        assert_eq!(
+            parse(2, "1x2x3\n2x3x4\u{a0}"),
+            (2, 5, 10..13, "4\u{a0}".into())
+        );
+    }
+
+    #[test]
+    fn parse_all() {
+        let problem = find(2015, 2).unwrap();
+        let errors = problem
+            .parse_all("1x2\n3x4x5\nZ\n6x7x8x9", 10)
+            .err()
+            .unwrap();
+        let lines: Vec<usize> = errors.iter().map(ParseError::line).collect();
+        assert_eq!(lines, [1, 3, 4]);
+        assert!(!errors.is_full());
+        assert_eq!(errors.to_string().lines().count(), 3);
+        let errors = problem.parse_all("Z\nZ\nZ", 2).err().unwrap();
+        assert_eq!((errors.len(), errors.is_full()), (2, true));
+        let first = problem.parse("1x2\nZ").err().unwrap();
+        assert_eq!(first.line(), 1);
+        let errors = find(2015, 1).unwrap().parse_all("(a)b", 5).err().unwrap();
+        let errors: Vec<_> = errors
+            .into_owned()
+            .into_iter()
+            .map(|err| err.pos())
+            .collect();
+        assert_eq!(errors, [2, 4]);
+        assert!(problem.parse_all("1x2x3", 5).is_ok());
+    }
+
+    #[test]
+    fn normalize() {
+        let all = Normalize::ALL;
+        assert_eq!(all.apply("\u{feff}a\r\nb\r\n"), "a\nb");
+        assert_eq!(all.apply("a\n\n"), "a\n");
+        assert_eq!(all.apply("a\rb"), "a\rb");
+        assert_eq!(all.bom(false).apply("\u{feff}a\n"), "\u{feff}a");
+        assert_eq!(all.crlf(false).apply("a\r\n"), "a\r");
+        assert_eq!(Normalize::NONE.apply("\u{feff}a\r\n"), "\u{feff}a\r\n");
+        let problem = find(2015, 1).unwrap();
+        assert_eq!(problem.solve_all("())\n").unwrap()[0], Answer::from(-1i64));
+        let strict = ParseOptions::new().normalize(Normalize::NONE);
+        assert!(problem.parse_with("())\n", &strict).is_err());
+    }
+
+    #[test]
+    fn normalize_span() {
+        let parse = |day, input| match find(2015, day).unwrap().parse(input) {
+            Err(err) => (err.line(), err.pos(), err.span(), err.arg().to_string()),
+            Ok(_) => panic!(),
+        };
+        assert_eq!(parse(1, "\u{feff}(x"), (1, 3, 4..5, "x".into()));
+        let input = "\u{feff}1x2x3\r\n4x5\r\n6x7x8\r\n";
+        assert_eq!(parse(2, input), (2, 4, 13..13, "".into()));
+        let input = "1x2x3\r\n4x5x6\r\n7xZx9\r\n";
+        assert_eq!(parse(2, input), (3, 3, 16..17, "Z".into()));
+        assert_eq!(parse(1, "((\r\n\r\n"), (1, 3, 2..4, "\r\n".into()));
+    }
+
+    #[test]
+    fn parse_reader() {
+        let errors = |input: &str, options: &ParseOptions| {
+            let problem = find(2015, 2).unwrap();
+            let streamed = problem.parse_reader(input.as_bytes(), options).unwrap();
+            let parsed = problem.parse_with(input, options);
+            let streamed = streamed.err().unwrap().into_iter();
+            let parsed = parsed.err().unwrap().into_iter();
+            let key = |err: ParseError| (err.line(), err.pos(), err.span(), err.to_string());
+            assert!(streamed.map(key).eq(parsed.map(key)), "{input:?}");
+        };
+        let options = ParseOptions::new().max_errors(10);
+        errors("1x2x3\n4xZx6\n7x8\n9x9x9x9", &options);
+        errors("\u{feff}1x2\r\n1x2x3\r\nZ\r\n", &options);
+        errors("\u{feff}1x2x3\n", &options.normalize(Normalize::NONE));
+        errors("1x2x3\r\n1x2x3\r", &options);
+        errors("1x2x3\n\n\n", &options);
+        errors("1x2x3\r\n\r\n\r\n", &options);
+        errors("1x2x3\n\n", &options.normalize(Normalize::NONE));
+        errors("1x2x3\r\r\n4x5x6\n", &options);
+        errors("1x2x3\r\n4x5x6\n", &options.normalize(Normalize::NONE));
+        for input in ["1x2x3\n\n", "1x2x3\r\n\r\n"] {
+            let problem = find(2015, 2).unwrap();
+            let streamed = problem.parse_reader(input.as_bytes(), &options).unwrap();
+            let parsed = problem.parse_with(input, &options);
+            assert!(streamed.is_ok() && parsed.is_ok(), "{input:?}");
+        }
+        let problem = find(2015, 2).unwrap();
+        let input = "\u{feff}2x3x4\r\n1x1x10\r\n";
+        let mut solver = problem
+            .parse_reader(input.as_bytes(), &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(solver.solve(1), Ok(Answer::from(101u64)));
+        let solver = find(2015, 1).unwrap().parse_reader(&b"())\n"[..], &options);
+        assert_eq!(solver.unwrap().unwrap().solve(1), Ok(Answer::from(-1i64)));
+        let err = problem
+            .parse_reader(&b"1x2x\xff"[..], &options)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn validate() {
+        let validate = |day, input| find(2015, day).unwrap().validate(input).unwrap();
+        let summary = validate(2, "2x3x4\n1x1x10\n");
+        assert_eq!((summary.lines(), summary.items()), (2, 2));
+        assert_eq!(summary.range(), Some((1, 10)));
+        assert_eq!(summary.to_string(), "2 lines, 2 items, values 1..=10");
+        assert_eq!(validate(1, "(()))\n").items(), 5);
+        assert_eq!(validate(3, "^>v<").range(), None);
+        assert_eq!(validate(5, "ab\nabcd").range(), Some((2, 4)));
+        assert_eq!(validate(2, "").to_string(), "0 lines, 0 items");
+        let err = find(2015, 5).unwrap().validate("abc\nAbc").unwrap_err();
+        assert_eq!((err.line(), err.pos()), (2, 1));
+    }
+
+    #[test]
+    fn solve_part() {
+        let problem = &super::PROBLEMS[0];
+        assert_eq!(problem.solve_part("(()", 1), Ok("1".into()));
+        assert_eq!(problem.solve_part("())", 2), Ok("3".into()));
+        let err = Err(Error::Solve(SolveError::NoSolution));
+        assert_eq!(problem.solve_part("(()", 2), err);
+        let err = Err(Error::Solve(SolveError::PartNotFound(3)));
+        assert_eq!(problem.solve_part("(()", 3), err);
+        assert!(matches!(problem.solve_part("(x", 1), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn solve_context() {
+        let ctx = SolveContext::new();
+        assert_eq!(ctx.check(), Ok(()));
+        let mut solver = PROBLEMS[0].parse("())").unwrap();
+        assert_eq!(solver.solve_with(1, &ctx), Ok((-1i64).into()));
+        ctx.clone().cancel();
+        assert!(ctx.is_cancelled());
+        assert_eq!(solver.solve_with(1, &ctx), Err(SolveError::Cancelled));
+        let ctx = SolveContext::new().deadline(Instant::now());
+        assert_eq!(ctx.check(), Err(SolveError::Timeout));
+        let ctx = SolveContext::new().timeout(Duration::ZERO);
+        assert_eq!(ctx.check(), Err(SolveError::Timeout));
+        assert_eq!(ctx.start().check(), Err(SolveError::Timeout));
+        let ctx = SolveContext::new().timeout(Duration::from_millis(20));
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(ctx.check(), Err(SolveError::Timeout));
+        assert_eq!(ctx.start().check(), Ok(()));
+        let ctx = SolveContext::new().timeout(Duration::from_secs(60));
+        assert_eq!(ctx.check(), Ok(()));
+        assert_eq!(ctx.start().check(), Ok(()));
+    }
+
+    #[test]
+    fn progress() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let ctx = SolveContext::new().progress(move |progress| {
+            log.lock()
+                .unwrap()
+                .push((progress.done(), progress.total()));
+        });
+        SolveContext::new().report(1, None);
+        ctx.report(5, None);
+        ctx.start().report(10, Some(20));
+        assert_eq!(*seen.lock().unwrap(), [(5, None), (10, Some(20))]);
+        let progress = Progress {
+            done: 50,
+            total: Some(200),
+            elapsed: Duration::from_secs(2),
+        };
+        assert_eq!(progress.rate(), 25.0);
+        assert_eq!(progress.to_string(), "50 iterations, 25/s, 25% of ~200");
+    }
+
+    #[test]
+    fn params() {
+        let problem = find(2015, 3).unwrap();
+        assert_eq!(problem.params()[0].to_string(), "santas=2 (1..=1000)");
+        assert!(PROBLEMS[0].params().is_empty());
+        assert_eq!(problem.parse_param("santas=3"), Ok(("santas", 3)));
+        for (arg, kind, pos) in [
+            ("santas", ErrorKind::UnexpectedEnd, 7),
+            ("elves=3", ErrorKind::InvalidCharacter, 1),
+            ("santas=x", ErrorKind::NumberFormat, 8),
+            ("santas=0", ErrorKind::OutOfRange, 8),
+        ] {
+            let err = problem.parse_param(arg).unwrap_err();
+            assert_eq!((err.kind(), err.pos()), (kind, pos));
+        }
+        let mut params = Params::new();
+        params.set("santas", 3);
+        params.set("santas", 4);
+        assert_eq!(params.iter().collect::<Vec<_>>(), [("santas", 4)]);
+        let ctx = SolveContext::new().params(params);
+        assert_eq!(problem.params()[0].value(&ctx), Ok(4));
+        assert_eq!(problem.params()[0].value(&SolveContext::new()), Ok(2));
+        let mut solver = problem.parse("^v^v^v^v^v").unwrap();
+        assert_eq!(solver.solve_with(2, &ctx), Ok(7u64.into()));
+    }
+
+    #[test]
+    fn algos() {
+        let problem = find(2015, 3).unwrap();
+        let names: Vec<_> = problem.algos().iter().map(Algo::name).collect();
+        assert_eq!(names, ["sort", "hashset"]);
+        assert_eq!(problem.default_algo().map(Algo::name), Some("sort"));
+        assert_eq!(problem.algo("hashset").map(Algo::name), Some("hashset"));
+        assert!(problem.algo("bitmap").is_none());
+        assert!(PROBLEMS[0].algo("sort").is_none());
+        for algo in problem.algos() {
+            let options = ParseOptions::new().algo(algo.name());
+            let mut solver = problem.parse_with("^>v<^v^v", &options).unwrap();
+            assert_eq!(solver.solve(1), Ok(4u64.into()));
+            assert_eq!(solver.solve(2), Ok(6u64.into()));
+            let reader = problem.parse_reader(&b"^>v<^v^v"[..], &options).unwrap();
+            assert_eq!(reader.unwrap().solve(1), Ok(4u64.into()));
+        }
+        let sort = ParseOptions::new().algo("sort");
+        let errors = PROBLEMS[0].parse_with("^v^v", &sort).err().unwrap();
+        let err = errors.iter().next().unwrap();
+        assert_eq!((err.msg(), err.arg()), ("Unknown algorithm", "sort"));
+        let reader = PROBLEMS[0].parse_reader(&b"()"[..], &sort).unwrap();
+        assert!(reader.is_err());
+    }
 }
@@ -1,29 +1,1127 @@
-use std::io::{stdin, Read};
-
-use aoc_solver::PROBLEMS;
-
-fn main() {
-    let mut args = std::env::args();
-    args.next().unwrap();
-    let year: u16 = args.next().unwrap().parse().unwrap();
-    let day: u8 = args.next().unwrap().parse().unwrap();
-    for problem in PROBLEMS {
-        if problem.year() == year && problem.day() == day {
-            let input = match args.next() {
-                Some(arg) => arg,
-                None => {
-                    let mut input = String::new();
-                    stdin().read_to_string(&mut input).unwrap();
-                    input
+use std::fs::File;
+use std::io::{self, stdin, BufRead, BufReader, IsTerminal, Read};
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{env, fmt, fs};
+
+use aoc_solver::answers::{Answers, Verdict, DEFAULT_ANSWERS_FILE};
+use aoc_solver::bench::{self, Baseline};
+use aoc_solver::diagnostic::Renderer;
+use aoc_solver::inputs::InputDir;
+use aoc_solver::output::{Failure, Record};
+use aoc_solver::runner::{self, Report};
+use aoc_solver::{
+    Algo, Answer, Error as LibError, ErrorKind, Normalize, Param, Params, ParseError, ParseOptions,
+    Problem, ProblemId, Progress, SolveContext, SolveError, PROBLEMS,
+};
+
+const USAGE: &str = "\
+Usage:
+    aoc-solver solve [OPTIONS] YEAR DAY [INPUT]
+    aoc-solver solve [OPTIONS] YEAR/DAY [INPUT]
+    aoc-solver YEAR DAY [INPUT]
+    aoc-solver list [--calendar] [YEAR]
+    aoc-solver run-all [--format FMT] [--input-dir DIR] [--strict] [--jobs N]
+                       [--timeout SECS] [YEAR]
+    aoc-solver verify [--format FMT] [--answers FILE] [--numeric] [--input-dir DIR]
+                      [--strict] [--jobs N] [--timeout SECS] [YEAR [DAY]]
+    aoc-solver bench [BENCH OPTIONS] YEAR DAY [INPUT]
+    aoc-solver bench [BENCH OPTIONS] --all [YEAR]
+    aoc-solver check [OPTIONS] YEAR DAY [INPUT]
+    aoc-solver check [OPTIONS] --all [YEAR]
+    aoc-solver help
+
+Options:
+    --part N         Solve only part N
+    --input FILE     Read puzzle input from FILE (- for stdin)
+    --input-dir DIR  Look for puzzle inputs in DIR
+    --format FMT     Print plain text, json or tsv records
+    --max-errors N   Report at most N parse errors (default 20)
+    --strict         Parse the input exactly as given
+    --stream         Parse the input while it is read, without keeping it
+    --timeout SECS   Give up on a part after SECS seconds
+    --param K=V      Solve a variant of the puzzle with parameter K set to V
+    --algo NAME      Solve with algorithm NAME instead of the default
+
+Bench options:
+    --runs N         Measure N runs (default 10)
+    --warmup N       Run N times before measuring (default 1)
+    --save FILE      Save median times to FILE
+    --baseline FILE  Compare median times with FILE
+    --threshold PCT  Report a regression above PCT percent (default 10)
+    --strict         Parse the input exactly as given
+    --algo NAME      Measure only algorithm NAME
+
+When neither INPUT nor --input is given, puzzle input is read from
+DIR/YEAR/DD.txt if it exists, and from stdin otherwise.  DIR defaults
+to $AOC_INPUT_DIR, or to inputs when that is not set.  A byte order
+mark is removed, CRLF line endings are converted to LF and the final
+newline is removed before the input is parsed, unless --strict is given.
+
+On a terminal, the solve command shows the progress of long searches
+on stderr while a part is being solved.
+
+The run-all command solves every puzzle which has an input file in DIR
+and prints the answers together with parse and solve times.  Puzzles
+are solved on N threads, one per core unless --jobs is given.
+
+The verify command solves the same puzzles and compares the answers
+with FILE, which defaults to DIR/answers.txt.  Each line of FILE holds
+YEAR DAY PART ANSWER; empty lines and lines starting with # are
+ignored.  With --numeric, integer answers are compared by value.
+Grid answers span several lines and cannot be verified.
+Puzzles with several algorithms are solved with each of them, and an
+answer which differs from the default algorithm counts as a mismatch.
+
+With --format json, one JSON object is printed per line for each part
+with the fields year, day, title, part, answer, duration (seconds),
+parse_duration, status, expected and error.  The error is null or an
+object with kind \"parse\" (code, line, pos, msg, arg, source, message),
+\"solve\" or \"io\" (message).  With --format tsv, the same fields are printed as
+tab separated values after a header line.
+
+The bench command parses the input and solves every part repeatedly
+and prints min, median, mean and standard deviation of each step.
+Puzzles with several algorithms are measured with each of them, and
+their answers are checked against the default algorithm.
+
+The check command only parses the input, without solving it, and
+prints the number of lines and items and the range of the values.
+
+Exit status:
+    0  success
+    1  I/O error
+    2  usage error
+    3  parse error
+    4  solve error
+    5  answer mismatch
+    6  benchmark regression";
+
+enum Error {
+    Io(io::Error),
+    Usage(String),
+    Parse(String),
+    Solve(String),
+    Mismatch(usize),
+    Regression(usize),
+}
+
+impl Error {
+    fn exit_code(&self) -> ExitCode {
+        ExitCode::from(match self {
+            Error::Io(_) => 1,
+            Error::Usage(_) => 2,
+            Error::Parse(_) => 3,
+            Error::Solve(_) => 4,
+            Error::Mismatch(_) => 5,
+            Error::Regression(_) => 6,
+        })
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{err}"),
+            Error::Usage(msg) => write!(f, "{msg}"),
+            Error::Parse(msg) => write!(f, "Parse error: {msg}"),
+            Error::Solve(msg) => write!(f, "{msg}"),
+            Error::Mismatch(count) => write!(f, "{count} answer(s) did not match"),
+            Error::Regression(count) => write!(f, "{count} time(s) regressed"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Plain,
+    Json,
+    Tsv,
+}
+
+impl Format {
+    fn parse(arg: &str) -> Result<Self, Error> {
+        match arg {
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(Error::Usage(format!("Invalid format: {arg}"))),
+        }
+    }
+    fn header(self) {
+        if self == Format::Tsv {
+            println!("{}", Record::TSV_HEADER.join("\t"));
+        }
+    }
+    fn print(self, record: &Record) {
+        match self {
+            Format::Plain => {}
+            Format::Json => println!("{}", record.json()),
+            Format::Tsv => println!("{}", record.tsv()),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(args.iter().map(String::as_str)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("aoc-solver: {err}");
+            if let Error::Usage(_) = err {
+                eprintln!("Try 'aoc-solver help' for more information.");
+            }
+            err.exit_code()
+        }
+    }
+}
+
+fn run<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    match args.next() {
+        None => Err(Error::Usage("Missing command".into())),
+        Some("help" | "-h" | "--help") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some("list") => list(args),
+        Some("solve") => solve(args),
+        Some("run-all") => run_all(args),
+        Some("verify") => verify(args),
+        Some("bench") => bench(args),
+        Some("check") => check(args),
+        Some(arg) if arg.starts_with(|ch: char| ch.is_ascii_digit()) => {
+            solve([arg].into_iter().chain(args))
+        }
+        Some(arg) => Err(Error::Usage(format!("Unknown command: {arg}"))),
+    }
+}
+
+fn list<'a>(args: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    let mut calendar = false;
+    let mut year = None;
+    for arg in args {
+        match arg {
+            "--calendar" => calendar = true,
+            _ if year.is_none() && !arg.starts_with('-') => year = Some(parse_year(Some(arg))?),
+            _ => return Err(Error::Usage(format!("Unexpected argument: {arg}"))),
+        }
+    }
+    if !calendar {
+        for problem in problems(year) {
+            let parts = problem.parts();
+            let plural = if parts == 1 { "" } else { "s" };
+            print!("{} {} ({parts} part{plural})", problem.year(), problem);
+            for param in problem.params() {
+                print!(" [{param}]");
+            }
+            if !problem.algos().is_empty() {
+                let names: Vec<_> = problem.algos().iter().map(Algo::name).collect();
+                print!(" [algo {}]", names.join("|"));
+            }
+            println!();
+        }
+        return Ok(());
+    }
+    let years: Vec<u16> = match year {
+        Some(year) => vec![year],
+        None => aoc_solver::years().collect(),
+    };
+    for (i, &year) in years.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_calendar(year);
+    }
+    Ok(())
+}
+
+fn print_calendar(year: u16) {
+    let mut solved = [false; 25];
+    for problem in aoc_solver::by_year(year) {
+        solved[problem.day() as usize - 1] = true;
+    }
+    let count = solved.iter().filter(|&&solved| solved).count();
+    println!("{year} ({count}/25)");
+    for (i, days) in solved.chunks(5).enumerate() {
+        let mut line = String::new();
+        for (j, &solved) in days.iter().enumerate() {
+            let mark = if solved { '*' } else { ' ' };
+            line += &format!(" {:2}{mark}", 5 * i + j + 1);
+        }
+        println!("{}", line.trim_end());
+    }
+}
+
+fn solve<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    let mut part = None;
+    let mut file = None;
+    let mut dir = InputDir::from_env();
+    let mut format = Format::Plain;
+    let mut options = ParseOptions::new().max_errors(20);
+    let mut stream = false;
+    let mut ctx = SolveContext::new();
+    let mut params = Vec::new();
+    let mut algo = None;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--format" => format = Format::parse(option_value(arg, args.next())?)?,
+            "--param" => params.push(option_value(arg, args.next())?),
+            "--algo" => algo = Some(option_value(arg, args.next())?),
+            "--part" => part = Some(parse_part(option_value(arg, args.next())?)?),
+            "--max-errors" => {
+                let max_errors = parse_count(arg, option_value(arg, args.next())?)?;
+                options = options.max_errors(max_errors);
+            }
+            "--strict" => options = options.normalize(Normalize::NONE),
+            "--stream" => stream = true,
+            "--timeout" => ctx = ctx.timeout(parse_timeout(arg, option_value(arg, args.next())?)?),
+            "--input" => file = Some(option_value(arg, args.next())?),
+            "--input-dir" => dir = InputDir::new(option_value(arg, args.next())?),
+            _ if arg.starts_with("--") => {
+                return Err(Error::Usage(format!("Unknown option: {arg}")));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+    let problem = find(parse_id(&mut positional)?)?;
+    let input = positional.next();
+    if let Some(arg) = positional.next() {
+        return Err(Error::Usage(format!("Unexpected argument: {arg}")));
+    }
+    if let Some(name) = algo {
+        options = options.algo(parse_algo(problem, name)?.name());
+    }
+    let ctx = ctx.params(parse_params(problem, params)?);
+    let (year, day) = (problem.year(), problem.day());
+    let parts = match part {
+        Some(part) if part > problem.parts() => {
+            let count = problem.parts();
+            let msg = format!("Invalid part: {part} ({year} Day {day} has {count} parts)");
+            return Err(Error::Usage(msg));
+        }
+        Some(part) => part..=part,
+        None => 1..=problem.parts(),
+    };
+    let reader = match stream {
+        true => Some(open_input(year, day, input, file, &dir)?),
+        false => None,
+    };
+    let input = match reader {
+        Some(_) => String::new(),
+        None => load_input(year, day, input, file, &dir)?,
+    };
+    format.header();
+    let start = Instant::now();
+    let parsed = match reader {
+        Some(reader) => problem.parse_reader(reader, &options).map_err(Error::Io)?,
+        None => problem.parse_with(&input, &options),
+    };
+    let mut solver = parsed.map_err(|errors| {
+        match format {
+            Format::Plain if stream => {
+                for err in &errors {
+                    eprintln!("error: {err}");
+                }
+            }
+            Format::Plain => eprint!("{}", renderer().render_all(&input, &errors)),
+            _ => {
+                for err in &errors {
+                    format.print(&failure(problem, Failure::parse(err)));
+                }
+            }
+        }
+        let mut errors = errors.into_iter();
+        let first = errors.next().map(|err| err.to_string()).unwrap_or_default();
+        match errors.count() {
+            0 => Error::Parse(first),
+            more => Error::Parse(format!("{first} (and {more} more)")),
+        }
+    })?;
+    let parse_time = start.elapsed();
+    let live = format == Format::Plain && io::stderr().is_terminal();
+    for part in parts {
+        let start = Instant::now();
+        let output = match live {
+            true => {
+                let output = solver.solve_with(part, &ctx.start().progress(progress_line(part)));
+                eprint!("\r\x1b[K");
+                output
+            }
+            false => solver.solve_with(part, &ctx.start()),
+        };
+        let mut record = Record::new(problem);
+        record.part = Some(part);
+        record.time = Some(start.elapsed());
+        record.parse_time = Some(parse_time);
+        match &output {
+            Ok(answer) => record.answer = Some(answer),
+            Err(err) => record.error = Some(Failure::solve(err)),
+        }
+        match (format, &output) {
+            (Format::Plain, Ok(answer)) => println!("{answer}"),
+            (Format::Plain, Err(_)) => {}
+            _ => format.print(&record),
+        }
+        if let Err(err) = output {
+            return Err(Error::Solve(format!("Part {part}: {err}")));
+        }
+    }
+    Ok(())
+}
+
+fn run_all<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    let mut dir = InputDir::from_env();
+    let mut format = Format::Plain;
+    let mut options = ParseOptions::new();
+    let mut jobs = default_jobs();
+    let mut ctx = SolveContext::new();
+    let mut year = None;
+    while let Some(arg) = args.next() {
+        match arg {
+            "--format" => format = Format::parse(option_value(arg, args.next())?)?,
+            "--input-dir" => dir = InputDir::new(option_value(arg, args.next())?),
+            "--strict" => options = options.normalize(Normalize::NONE),
+            "--jobs" | "-j" => jobs = parse_count(arg, option_value(arg, args.next())?)?,
+            "--timeout" => ctx = ctx.timeout(parse_timeout(arg, option_value(arg, args.next())?)?),
+            _ if year.is_none() && !arg.starts_with('-') => year = Some(parse_year(Some(arg))?),
+            _ => return Err(Error::Usage(format!("Unexpected argument: {arg}"))),
+        }
+    }
+    let (mut unread, mut failed) = (0, (0, 0));
+    let years: Vec<u16> = aoc_solver::years()
+        .filter(|&y| year.is_none_or(|year| y == year))
+        .collect();
+    let problems = years.iter().flat_map(|&year| aoc_solver::by_year(year));
+    let inputs = read_inputs(&dir, problems);
+    let mut results = solve_inputs(inputs, jobs, &options, &ctx)
+        .into_iter()
+        .peekable();
+    match format {
+        Format::Plain => println!(
+            "{:<4} {:>3}  {:>9}  {:<20} {:>9}  {:<20} {:>9}",
+            "YEAR", "DAY", "PARSE", "PART 1", "TIME", "PART 2", "TIME"
+        ),
+        _ => format.header(),
+    }
+    for year in years {
+        let mut count = 0;
+        let mut total = Duration::ZERO;
+        while let Some((problem, input, result)) =
+            results.next_if(|(problem, _, _)| problem.year() == year)
+        {
+            count += 1;
+            let day = problem.day();
+            let report = match result {
+                Ok(report) => report,
+                Err(err) => {
+                    match err {
+                        LibError::Io(_) => unread += 1,
+                        _ => failed.0 += 1,
+                    }
+                    match format {
+                        Format::Plain => println!("{year:<4} {day:>3}  {}", describe(&input, &err)),
+                        _ => format.print(&failure(problem, Failure::new(&err))),
+                    }
+                    continue;
+                }
+            };
+            total += report.total_time();
+            if format != Format::Plain {
+                for record in records(&report) {
+                    failed.1 += record.error.is_some() as usize;
+                    format.print(&record);
+                }
+                continue;
+            }
+            let mut line = format!(
+                "{year:<4} {day:>3}  {:>9}",
+                fmt_duration(report.parse_time())
+            );
+            for part in 1..=2 {
+                let (answer, time) = match report.part(part) {
+                    Some(report) => match report.answer() {
+                        Ok(answer) => (answer.to_string(), fmt_duration(report.time())),
+                        Err(err) => {
+                            failed.1 += 1;
+                            (format!("error: {err}"), fmt_duration(report.time()))
+                        }
+                    },
+                    None => ("-".into(), "-".into()),
+                };
+                line += &format!("  {answer:<20} {time:>9}");
+            }
+            println!("{}", line.trim_end());
+        }
+        if format == Format::Plain {
+            let total = fmt_duration(total);
+            let skipped = aoc_solver::by_year(year).len() - count;
+            println!("{year} total: {total} ({count} days, {skipped} skipped)");
+        }
+    }
+    match (unread, failed) {
+        (0, (0, 0)) => Ok(()),
+        (0, (0, count)) => Err(Error::Solve(format!("{count} part(s) failed"))),
+        (0, (count, _)) => Err(Error::Parse(format!("{count} input(s) failed to parse"))),
+        (count, _) => Err(unread_error(count)),
+    }
+}
+
+fn verify<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    let mut dir = InputDir::from_env();
+    let mut file = None;
+    let mut format = Format::Plain;
+    let mut numeric = false;
+    let mut options = ParseOptions::new();
+    let mut jobs = default_jobs();
+    let mut ctx = SolveContext::new();
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--answers" => file = Some(option_value(arg, args.next())?),
+            "--format" => format = Format::parse(option_value(arg, args.next())?)?,
+            "--numeric" => numeric = true,
+            "--jobs" | "-j" => jobs = parse_count(arg, option_value(arg, args.next())?)?,
+            "--timeout" => ctx = ctx.timeout(parse_timeout(arg, option_value(arg, args.next())?)?),
+            "--strict" => options = options.normalize(Normalize::NONE),
+            "--input-dir" => dir = InputDir::new(option_value(arg, args.next())?),
+            _ if arg.starts_with('-') => {
+                return Err(Error::Usage(format!("Unknown option: {arg}")));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+    let (year, day) = match positional.next() {
+        Some(arg) if arg.contains(['/', '-']) => {
+            let id = parse_id(&mut [arg].into_iter())?;
+            (Some(id.year()), Some(id.day()))
+        }
+        Some(arg) => {
+            let year = parse_year(Some(arg))?;
+            let day = positional.next().map(|arg| parse_day(Some(arg)));
+            (Some(year), day.transpose()?)
+        }
+        None => (None, None),
+    };
+    if let Some(arg) = positional.next() {
+        return Err(Error::Usage(format!("Unexpected argument: {arg}")));
+    }
+    let path = match file {
+        Some(file) => file.into(),
+        None => dir.root().join(DEFAULT_ANSWERS_FILE),
+    };
+    let text = fs::read_to_string(&path).map_err(|err| {
+        let path = path.display();
+        Error::Io(io::Error::new(err.kind(), format!("{path}: {err}")))
+    })?;
+    let answers =
+        Answers::parse(&text).map_err(|err| Error::Parse(format!("{}: {err}", path.display())))?;
+    let (mut matched, mut mismatched, mut missing, mut failed) = (0, 0, 0, (0, 0));
+    let (mut unread, mut unsupported) = (0, 0);
+    format.header();
+    let problems = problems(year)
+        .iter()
+        .filter(|problem| day.is_none_or(|day| problem.day() == day));
+    let inputs = read_inputs(&dir, problems);
+    for (problem, input, result) in solve_inputs(inputs, jobs, &options, &ctx) {
+        let (year, day) = (problem.year(), problem.day());
+        let report = match result {
+            Ok(report) => report,
+            Err(err) => {
+                match err {
+                    LibError::Io(_) => unread += 1,
+                    _ => failed.0 += 1,
+                }
+                match format {
+                    Format::Plain => println!("{year} Day {day}: {}", describe(&input, &err)),
+                    _ => format.print(&failure(problem, Failure::new(&err))),
+                }
+                continue;
+            }
+        };
+        for mut record in records(&report) {
+            let part = record.part.unwrap_or_default();
+            let Some(answer) = record.answer else {
+                failed.1 += 1;
+                match format {
+                    Format::Plain => {
+                        let err = record.error.as_ref().map_or("", Failure::message);
+                        println!("{year} Day {day} part {part}: error: {err}");
+                    }
+                    _ => format.print(&record),
+                }
+                continue;
+            };
+            let verdict = match answers.verify(year, day, part, answer, numeric) {
+                Verdict::Match => {
+                    matched += 1;
+                    record.status = Some("match");
+                    "match".to_string()
+                }
+                Verdict::Mismatch(expect) => {
+                    mismatched += 1;
+                    record.status = Some("mismatch");
+                    record.expected = Some(expect);
+                    format!("MISMATCH (expected {expect}, got {answer})")
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    record.status = Some("missing");
+                    format!("missing (got {answer})")
+                }
+                Verdict::Unsupported => {
+                    unsupported += 1;
+                    record.status = Some("unsupported");
+                    "unsupported (grid answers cannot be verified)".to_string()
+                }
+            };
+            match format {
+                Format::Plain => println!("{year} Day {day} part {part}: {verdict}"),
+                _ => format.print(&record),
+            }
+        }
+        let disagreements = match runner::compare_algos(&report, &input, &options, &ctx) {
+            Ok(disagreements) => disagreements,
+            Err(err) => {
+                failed.0 += 1;
+                match format {
+                    Format::Plain => println!("{year} Day {day}: {}", describe(&input, &err)),
+                    _ => format.print(&failure(problem, Failure::new(&err))),
+                }
+                continue;
+            }
+        };
+        for (algo, other) in &disagreements {
+            mismatched += 1;
+            let part = other.part();
+            let mut record = Record::new(problem);
+            record.part = Some(part);
+            record.time = Some(other.time());
+            record.status = Some("disagree");
+            match other.answer() {
+                Ok(answer) => record.answer = Some(answer),
+                Err(err) => record.error = Some(Failure::solve(err)),
+            }
+            match (format, other.answer()) {
+                (Format::Plain, Ok(answer)) => {
+                    println!("{year} Day {day} part {part}: DISAGREE (algo {algo} got {answer})")
                 }
+                (Format::Plain, Err(err)) => {
+                    println!("{year} Day {day} part {part}: DISAGREE (algo {algo} error: {err})")
+                }
+                _ => format.print(&record),
+            }
+        }
+    }
+    if format == Format::Plain {
+        let mut summary = format!("{matched} match, {mismatched} mismatch, {missing} missing");
+        if unsupported > 0 {
+            summary += &format!(", {unsupported} unsupported");
+        }
+        println!("{summary}");
+    }
+    match (mismatched, unread, failed) {
+        (0, 0, (0, 0)) => Ok(()),
+        (0, 0, (0, count)) => Err(Error::Solve(format!("{count} part(s) failed"))),
+        (0, 0, (count, _)) => Err(Error::Parse(format!("{count} input(s) failed to parse"))),
+        (0, count, _) => Err(unread_error(count)),
+        (count, _, _) => Err(Error::Mismatch(count)),
+    }
+}
+
+fn bench<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    let mut dir = InputDir::from_env();
+    let mut file = None;
+    let mut all = false;
+    let mut runs = 10;
+    let mut warmup = 1;
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut options = ParseOptions::new();
+    let mut algo = None;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--all" => all = true,
+            "--algo" => algo = Some(option_value(arg, args.next())?),
+            "--strict" => options = options.normalize(Normalize::NONE),
+            "--runs" => runs = parse_count(arg, option_value(arg, args.next())?)?,
+            "--warmup" => warmup = parse_count(arg, option_value(arg, args.next())?)?,
+            "--save" => save = Some(option_value(arg, args.next())?),
+            "--baseline" => baseline = Some(option_value(arg, args.next())?),
+            "--threshold" => {
+                let value = option_value(arg, args.next())?;
+                threshold = match value.parse::<f64>() {
+                    Ok(value) if value >= 0.0 => value,
+                    _ => return Err(Error::Usage(format!("Invalid {arg}: {value}"))),
+                };
+            }
+            "--input" => file = Some(option_value(arg, args.next())?),
+            "--input-dir" => dir = InputDir::new(option_value(arg, args.next())?),
+            _ if arg.starts_with("--") => {
+                return Err(Error::Usage(format!("Unknown option: {arg}")));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    if runs == 0 {
+        return Err(Error::Usage("Invalid --runs: 0".into()));
+    }
+    let jobs = collect_jobs(positional, all, file, &dir)?;
+    let baseline = match baseline {
+        Some(file) => {
+            let text = read_file(file)?;
+            let baseline =
+                Baseline::parse(&text).map_err(|err| Error::Parse(format!("{file}: {err}")))?;
+            Some(baseline)
+        }
+        None => None,
+    };
+    let mut results = Baseline::new();
+    let mut regressions = 0;
+    let mut disagreements = 0;
+    let mut unread = 0;
+    for (problem, input) in &jobs {
+        let (year, day) = (problem.year(), problem.day());
+        let input = match input {
+            Ok(input) => input,
+            Err(err) => {
+                unread += 1;
+                println!("{year} {problem}: {err}");
+                continue;
+            }
+        };
+        let algos: Vec<_> = match algo {
+            Some(name) => vec![Some(parse_algo(problem, name)?)],
+            None if problem.algos().len() > 1 => problem.algos().iter().map(Some).collect(),
+            None => vec![None],
+        };
+        let mut first: Option<(&str, Vec<Answer>)> = None;
+        for algo in algos {
+            let options = match algo {
+                Some(algo) => options.algo(algo.name()),
+                None => options,
             };
-            let mut solver = problem.parse(&input).unwrap();
-            for part in 1..=problem.parts() {
-                let output = solver.solve(part).unwrap();
-                println!("{}", output);
+            let ctx = SolveContext::new();
+            let bench = bench::bench(problem, input, warmup, runs, &options, &ctx).map_err(
+                |err| match err {
+                    LibError::Parse(err) => {
+                        diagnose(input, &err);
+                        Error::Parse(format!("{year} Day {day}: {err}"))
+                    }
+                    LibError::Solve(err) => Error::Solve(format!("{year} Day {day}: {err}")),
+                    LibError::Io(err) => Error::Io(err),
+                },
+            )?;
+            match algo {
+                Some(algo) => println!("{year} {problem} ({runs} runs, {warmup} warm-up, {algo})"),
+                None => println!("{year} {problem} ({runs} runs, {warmup} warm-up)"),
+            }
+            let stages = [(None, bench.parse())].into_iter().chain(
+                bench
+                    .parts()
+                    .iter()
+                    .map(|(part, stats)| (Some(*part), stats)),
+            );
+            for (part, stats) in stages {
+                let label = part.map_or("parse".into(), |part| format!("part {part}"));
+                let mut line = format!(
+                    "  {label:<7} min {:>9}  median {:>9}  mean {:>9}  sd {:>9}",
+                    fmt_duration(stats.min()),
+                    fmt_duration(stats.median()),
+                    fmt_duration(stats.mean()),
+                    fmt_duration(stats.stddev()),
+                );
+                let old = baseline.as_ref().and_then(|base| base.get(year, day, part));
+                if let Some(old) = old.filter(|_| first.is_none()) {
+                    let change = 100.0 * bench::change(old, stats.median());
+                    line += &format!("  {change:+.1}% vs {}", fmt_duration(old));
+                    if change > threshold {
+                        regressions += 1;
+                        line += " REGRESSION";
+                    }
+                }
+                println!("{line}");
+            }
+            let name = algo.map_or("", Algo::name);
+            match &first {
+                None => {
+                    results.insert(&bench);
+                    first = Some((name, bench.answers().to_vec()));
+                }
+                Some((expect_algo, expect)) => {
+                    for (part, (answer, expect)) in (1..).zip(bench.answers().iter().zip(expect)) {
+                        if answer != expect {
+                            disagreements += 1;
+                            println!(
+                                "  part {part}: DISAGREE ({name} got {answer}, {expect_algo} got {expect})"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if let Some(file) = save {
+        fs::write(file, results.to_string())
+            .map_err(|err| Error::Io(io::Error::new(err.kind(), format!("{file}: {err}"))))?;
+    }
+    match (disagreements, regressions, unread) {
+        (0, 0, 0) => Ok(()),
+        (0, 0, count) => Err(unread_error(count)),
+        (0, count, _) => Err(Error::Regression(count)),
+        (count, _, _) => Err(Error::Mismatch(count)),
+    }
+}
+
+fn check<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    let mut dir = InputDir::from_env();
+    let mut file = None;
+    let mut all = false;
+    let mut options = ParseOptions::new().max_errors(20);
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--all" => all = true,
+            "--max-errors" => {
+                let max_errors = parse_count(arg, option_value(arg, args.next())?)?;
+                options = options.max_errors(max_errors);
+            }
+            "--strict" => options = options.normalize(Normalize::NONE),
+            "--input" => file = Some(option_value(arg, args.next())?),
+            "--input-dir" => dir = InputDir::new(option_value(arg, args.next())?),
+            _ if arg.starts_with("--") => {
+                return Err(Error::Usage(format!("Unknown option: {arg}")));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let jobs = collect_jobs(positional, all, file, &dir)?;
+    let (mut failed, mut unread) = (Vec::new(), 0);
+    for (problem, input) in &jobs {
+        let (year, day) = (problem.year(), problem.day());
+        let input = match input {
+            Ok(input) => input,
+            Err(err) => {
+                unread += 1;
+                println!("{year} Day {day}: {err}");
+                continue;
+            }
+        };
+        match problem.validate_with(input, &options) {
+            Ok(summary) => println!("{year} Day {day}: {summary}"),
+            Err(errors) => {
+                println!("{year} Day {day}: invalid input");
+                eprint!("{}", renderer().render_all(input, &errors));
+                failed.push(format!("{year} Day {day}"));
+            }
+        }
+    }
+    match (unread, failed.is_empty()) {
+        (0, true) => Ok(()),
+        (0, false) => Err(Error::Parse(format!(
+            "Invalid input: {}",
+            failed.join(", ")
+        ))),
+        (count, _) => Err(unread_error(count)),
+    }
+}
+
+fn records(report: &Report) -> Vec<Record<'_>> {
+    report
+        .parts()
+        .iter()
+        .map(|part| {
+            let mut record = Record::new(report.problem());
+            record.part = Some(part.part());
+            record.time = Some(part.time());
+            record.parse_time = Some(report.parse_time());
+            match part.answer() {
+                Ok(answer) => record.answer = Some(answer),
+                Err(err) => record.error = Some(Failure::solve(err)),
             }
-            return;
+            record
+        })
+        .collect()
+}
+
+fn describe(input: &str, err: &LibError) -> String {
+    if let LibError::Parse(err) = err {
+        diagnose(input, err);
+    }
+    format!("{err}: {}", Failure::new(err).message())
+}
+
+fn failure<'a>(problem: &'a Problem, err: Failure) -> Record<'a> {
+    let mut record = Record::new(problem);
+    record.error = Some(err);
+    record
+}
+
+fn diagnose(input: &str, err: &ParseError) {
+    eprint!("{}", renderer().render(input, err));
+}
+
+fn progress_line(part: u8) -> impl Fn(&Progress) + Send + Sync {
+    let shown = Mutex::new(Instant::now());
+    move |progress| {
+        let mut shown = shown.lock().unwrap();
+        if shown.elapsed() >= Duration::from_millis(100) {
+            eprint!("\rPart {part}: {progress}\x1b[K");
+            *shown = Instant::now();
         }
     }
-    panic!("Not implemented");
+}
+
+fn renderer() -> Renderer {
+    let color = io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+    Renderer::new().color(color)
+}
+
+fn fmt_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{secs:.2}s")
+    } else if secs >= 1e-3 {
+        format!("{:.2}ms", secs * 1e3)
+    } else if secs >= 1e-6 {
+        format!("{:.1}µs", secs * 1e6)
+    } else {
+        format!("{}ns", duration.as_nanos())
+    }
+}
+
+fn parse_id<'a>(args: &mut impl Iterator<Item = &'a str>) -> Result<ProblemId, Error> {
+    match args.next() {
+        Some(arg) if arg.contains(['/', '-']) => arg
+            .parse()
+            .map_err(|err| Error::Usage(format!("Invalid puzzle: {err}"))),
+        arg => {
+            let year = parse_year(arg)?;
+            let day = parse_day(args.next())?;
+            Ok(ProblemId::new(year, day).unwrap())
+        }
+    }
+}
+
+fn parse_year(arg: Option<&str>) -> Result<u16, Error> {
+    let arg = arg.ok_or_else(|| Error::Usage("Missing YEAR".into()))?;
+    match arg.parse() {
+        Ok(year) if year >= 2015 => Ok(year),
+        _ => Err(Error::Usage(format!("Invalid year: {arg}"))),
+    }
+}
+
+fn parse_day(arg: Option<&str>) -> Result<u8, Error> {
+    let arg = arg.ok_or_else(|| Error::Usage("Missing DAY".into()))?;
+    match arg.parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(Error::Usage(format!("Invalid day: {arg}"))),
+    }
+}
+
+fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |jobs| jobs.get())
+}
+
+fn parse_count(name: &str, arg: &str) -> Result<usize, Error> {
+    arg.parse()
+        .map_err(|_| Error::Usage(format!("Invalid {name}: {arg}")))
+}
+
+fn parse_timeout(name: &str, arg: &str) -> Result<Duration, Error> {
+    match arg.parse::<f64>().map(Duration::try_from_secs_f64) {
+        Ok(Ok(timeout)) if !timeout.is_zero() => Ok(timeout),
+        _ => Err(Error::Usage(format!("Invalid {name}: {arg}"))),
+    }
+}
+
+fn parse_algo(problem: &Problem, name: &str) -> Result<&'static Algo, Error> {
+    problem.algo(name).ok_or_else(|| {
+        let names: Vec<_> = problem.algos().iter().map(Algo::name).collect();
+        let hint = match names.is_empty() {
+            true => format!("{} {problem} has one algorithm", problem.year()),
+            false => format!("expected {}", names.join(", ")),
+        };
+        Error::Usage(format!("Invalid --algo {name} ({hint})"))
+    })
+}
+
+fn parse_params(problem: &Problem, args: Vec<&str>) -> Result<Params, Error> {
+    let mut params = Params::new();
+    for arg in args {
+        let (name, value) = problem.parse_param(arg).map_err(|err| {
+            let names: Vec<_> = problem.params().iter().map(Param::name).collect();
+            let param = problem.params().iter().find(|param| {
+                arg.split_once('=')
+                    .is_some_and(|(name, _)| name == param.name())
+            });
+            let hint = match (err.kind(), param) {
+                (ErrorKind::InvalidCharacter, _) if names.is_empty() => {
+                    format!(" ({} {problem} has no parameters)", problem.year())
+                }
+                (ErrorKind::InvalidCharacter, _) => format!(" (expected {})", names.join(", ")),
+                (_, Some(param)) => format!(" (expected {:?})", param.range()),
+                (_, None) => String::new(),
+            };
+            Error::Usage(format!("Invalid --param {arg}: {}{hint}", err.msg()))
+        })?;
+        params.set(name, value);
+    }
+    Ok(params)
+}
+
+fn parse_part(arg: &str) -> Result<u8, Error> {
+    match arg.parse() {
+        Ok(part) if part > 0 => Ok(part),
+        _ => Err(Error::Usage(format!("Invalid part: {arg}"))),
+    }
+}
+
+fn option_value<'a>(name: &str, value: Option<&'a str>) -> Result<&'a str, Error> {
+    value.ok_or_else(|| Error::Usage(format!("Missing value for {name}")))
+}
+
+fn find(id: ProblemId) -> Result<&'static Problem, Error> {
+    id.problem().ok_or_else(|| {
+        let (year, day) = (id.year(), id.day());
+        let err = SolveError::NotImplemented;
+        Error::Solve(format!("{year} Day {day}: {err}"))
+    })
+}
+
+fn problems(year: Option<u16>) -> &'static [Problem] {
+    match year {
+        Some(year) => aoc_solver::by_year(year),
+        None => &PROBLEMS,
+    }
+}
+
+fn load_input(
+    year: u16,
+    day: u8,
+    arg: Option<&str>,
+    file: Option<&str>,
+    dir: &InputDir,
+) -> Result<String, Error> {
+    match (arg, file) {
+        (Some(_), Some(_)) => Err(Error::Usage("INPUT and --input are exclusive".into())),
+        (Some(arg), None) => Ok(arg.to_string()),
+        (None, Some(file)) => read_file(file),
+        (None, None) => match dir.read(year, day).map_err(Error::Io)? {
+            Some(input) => Ok(input),
+            None => read_stdin(),
+        },
+    }
+}
+
+fn open_input<'a>(
+    year: u16,
+    day: u8,
+    arg: Option<&'a str>,
+    file: Option<&str>,
+    dir: &InputDir,
+) -> Result<Box<dyn BufRead + 'a>, Error> {
+    match (arg, file) {
+        (Some(_), Some(_)) => Err(Error::Usage("INPUT and --input are exclusive".into())),
+        (Some(arg), None) => Ok(Box::new(arg.as_bytes())),
+        (None, Some("-")) => Ok(Box::new(stdin().lock())),
+        (None, Some(file)) => match File::open(file) {
+            Ok(file) => Ok(Box::new(BufReader::new(file))),
+            Err(err) => Err(Error::Io(io::Error::new(
+                err.kind(),
+                format!("{file}: {err}"),
+            ))),
+        },
+        (None, None) => match dir.open(year, day).map_err(Error::Io)? {
+            Some(file) => Ok(Box::new(BufReader::new(file))),
+            None => Ok(Box::new(stdin().lock())),
+        },
+    }
+}
+
+fn read_inputs(
+    dir: &InputDir,
+    problems: impl IntoIterator<Item = &'static Problem>,
+) -> Vec<(&'static Problem, io::Result<String>)> {
+    problems
+        .into_iter()
+        .filter_map(|problem| {
+            let input = dir.read(problem.year(), problem.day()).transpose()?;
+            Some((problem, input))
+        })
+        .collect()
+}
+
+fn collect_jobs(
+    positional: Vec<&str>,
+    all: bool,
+    file: Option<&str>,
+    dir: &InputDir,
+) -> Result<Vec<(&'static Problem, io::Result<String>)>, Error> {
+    let mut positional = positional.into_iter();
+    let jobs = if all {
+        let year = positional
+            .next()
+            .map(|arg| parse_year(Some(arg)))
+            .transpose()?;
+        if file.is_some() {
+            return Err(Error::Usage("--input cannot be used with --all".into()));
+        }
+        read_inputs(dir, problems(year))
+    } else {
+        let problem = find(parse_id(&mut positional)?)?;
+        let (year, day) = (problem.year(), problem.day());
+        let input = positional.next();
+        vec![(problem, Ok(load_input(year, day, input, file, dir)?))]
+    };
+    if let Some(arg) = positional.next() {
+        return Err(Error::Usage(format!("Unexpected argument: {arg}")));
+    }
+    Ok(jobs)
+}
+
+fn solve_inputs(
+    inputs: Vec<(&'static Problem, io::Result<String>)>,
+    jobs: usize,
+    options: &ParseOptions,
+    ctx: &SolveContext,
+) -> Vec<(&'static Problem, String, Result<Report, LibError>)> {
+    let pool: Vec<_> = inputs
+        .iter()
+        .filter_map(|(problem, input)| Some((*problem, input.as_ref().ok()?.as_str())))
+        .collect();
+    let mut solved = runner::run_pool(&pool, jobs, options, ctx).into_iter();
+    inputs
+        .into_iter()
+        .map(|(problem, input)| match input {
+            Ok(input) => (problem, input, solved.next().unwrap()),
+            Err(err) => (problem, String::new(), Err(LibError::Io(err))),
+        })
+        .collect()
+}
+
+fn unread_error(count: usize) -> Error {
+    Error::Io(io::Error::other(format!(
+        "{count} input(s) could not be read"
+    )))
+}
+
+fn read_file(file: &str) -> Result<String, Error> {
+    match file {
+        "-" => read_stdin(),
+        _ => fs::read_to_string(file)
+            .map_err(|err| Error::Io(io::Error::new(err.kind(), format!("{file}: {err}")))),
+    }
+}
+
+fn read_stdin() -> Result<String, Error> {
+    let mut input = String::new();
+    stdin().read_to_string(&mut input).map_err(Error::Io)?;
+    Ok(input)
 }
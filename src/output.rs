@@ -0,0 +1,247 @@
+use std::time::Duration;
+
+use super::{Answer, Error, ErrorKind, ParseError, Problem, SolveError};
+
+pub enum Failure {
+    Parse {
+        code: ErrorKind,
+        line: usize,
+        pos: usize,
+        msg: &'static str,
+        arg: String,
+        source: Option<String>,
+        message: String,
+    },
+    Other(&'static str, String),
+}
+
+impl Failure {
+    pub fn parse(err: &ParseError) -> Self {
+        Failure::Parse {
+            code: err.kind(),
+            line: err.line(),
+            pos: err.pos(),
+            msg: err.msg(),
+            arg: err.arg().to_string(),
+            source: std::error::Error::source(err).map(ToString::to_string),
+            message: err.to_string(),
+        }
+    }
+    pub fn solve(err: &SolveError) -> Self {
+        Failure::Other("solve", err.to_string())
+    }
+    pub fn new(err: &Error) -> Self {
+        match err {
+            Error::Parse(err) => Failure::parse(err),
+            Error::Solve(err) => Failure::solve(err),
+            Error::Io(err) => Failure::Other("io", err.to_string()),
+        }
+    }
+    pub fn message(&self) -> &str {
+        match self {
+            Failure::Parse { message, .. } | Failure::Other(_, message) => message,
+        }
+    }
+    pub fn json(&self) -> String {
+        match self {
+            Failure::Parse {
+                code,
+                line,
+                pos,
+                msg,
+                arg,
+                source,
+                message,
+            } => format!(
+                "{{\"kind\":\"parse\",\"code\":\"{}\",\"line\":{line},\"pos\":{pos},\"msg\":{},\"arg\":{},\"source\":{},\"message\":{}}}",
+                code.code(),
+                json_str(msg),
+                json_str(arg),
+                source.as_deref().map_or("null".into(), json_str),
+                json_str(message),
+            ),
+            Failure::Other(kind, message) => {
+                format!("{{\"kind\":\"{kind}\",\"message\":{}}}", json_str(message))
+            }
+        }
+    }
+}
+
+pub struct Record<'a> {
+    pub problem: &'a Problem,
+    pub part: Option<u8>,
+    pub answer: Option<&'a Answer>,
+    pub time: Option<Duration>,
+    pub parse_time: Option<Duration>,
+    pub status: Option<&'static str>,
+    pub expected: Option<&'a str>,
+    pub error: Option<Failure>,
+}
+
+impl<'a> Record<'a> {
+    pub const TSV_HEADER: [&'static str; 10] = [
+        "year",
+        "day",
+        "title",
+        "part",
+        "answer",
+        "duration",
+        "parse_duration",
+        "status",
+        "expected",
+        "error",
+    ];
+
+    pub fn new(problem: &'a Problem) -> Self {
+        Record {
+            problem,
+            part: None,
+            answer: None,
+            time: None,
+            parse_time: None,
+            status: None,
+            expected: None,
+            error: None,
+        }
+    }
+
+    pub fn json(&self) -> String {
+        let null = || "null".to_string();
+        let secs = |time: Duration| time.as_secs_f64().to_string();
+        format!(
+            "{{\"year\":{},\"day\":{},\"title\":{},\"part\":{},\"answer\":{},\"duration\":{},\"parse_duration\":{},\"status\":{},\"expected\":{},\"error\":{}}}",
+            self.problem.year(),
+            self.problem.day(),
+            json_str(self.problem.title()),
+            self.part.map_or_else(null, |part| part.to_string()),
+            self.answer.map_or_else(null, |answer| json_str(&answer.to_string())),
+            self.time.map_or_else(null, secs),
+            self.parse_time.map_or_else(null, secs),
+            self.status.map_or_else(null, json_str),
+            self.expected.map_or_else(null, json_str),
+            self.error.as_ref().map_or_else(null, Failure::json),
+        )
+    }
+
+    pub fn tsv(&self) -> String {
+        let secs = |time: Duration| format!("{:.9}", time.as_secs_f64());
+        [
+            self.problem.year().to_string(),
+            self.problem.day().to_string(),
+            tsv_str(self.problem.title()),
+            self.part.map(|part| part.to_string()).unwrap_or_default(),
+            self.answer
+                .map(|answer| tsv_str(&answer.to_string()))
+                .unwrap_or_default(),
+            self.time.map(secs).unwrap_or_default(),
+            self.parse_time.map(secs).unwrap_or_default(),
+            self.status.unwrap_or_default().to_string(),
+            self.expected.map(tsv_str).unwrap_or_default(),
+            self.error
+                .as_ref()
+                .map(|err| tsv_str(err.message()))
+                .unwrap_or_default(),
+        ]
+        .join("\t")
+    }
+}
+
+pub fn json_str(text: &str) -> String {
+    let mut res = String::with_capacity(text.len() + 2);
+    res.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            _ if ch.is_control() => res.push_str(&format!("\\u{:04x}", ch as u32)),
+            _ => res.push(ch),
+        }
+    }
+    res.push('"');
+    res
+}
+
+pub fn tsv_str(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            _ => res.push(ch),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find;
+
+    #[test]
+    fn escape() {
+        assert_eq!(json_str("plain"), "\"plain\"");
+        assert_eq!(json_str("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(json_str("1\n2\r3\t4"), "\"1\\n2\\r3\\t4\"");
+        assert_eq!(json_str("\u{1}\u{7f}é"), "\"\\u0001\\u007fé\"");
+        assert_eq!(tsv_str("a\tb\nc\rd\\e\"f"), "a\\tb\\nc\\rd\\\\e\"f");
+    }
+
+    #[test]
+    fn failure() {
+        let err = find(2015, 2).unwrap().parse("1x2x\"").err().unwrap();
+        assert_eq!(
+            Failure::parse(&err).json(),
+            concat!(
+                "{\"kind\":\"parse\",\"code\":\"NumberFormat\",\"line\":1,\"pos\":5,",
+                "\"msg\":\"Invalid number\",\"arg\":\"\\\"\",",
+                "\"source\":\"invalid digit found in string\",",
+                "\"message\":\"1:5 Invalid number: invalid digit found in string: \\\"\"}"
+            )
+        );
+        let err = Failure::solve(&SolveError::NoSolution);
+        assert_eq!(
+            err.json(),
+            "{\"kind\":\"solve\",\"message\":\"No solution\"}"
+        );
+        let err = Failure::new(&Error::Io(std::io::Error::other("disk\nfull")));
+        assert_eq!(err.json(), "{\"kind\":\"io\",\"message\":\"disk\\nfull\"}");
+        assert_eq!(err.message(), "disk\nfull");
+    }
+
+    #[test]
+    fn record() {
+        let problem = find(2015, 1).unwrap();
+        let answer = Answer::from(-1i64);
+        let mut record = Record::new(problem);
+        record.part = Some(1);
+        record.answer = Some(&answer);
+        record.time = Some(Duration::from_millis(1500));
+        record.status = Some("mismatch");
+        record.expected = Some("say \"hi\"");
+        assert_eq!(
+            record.json(),
+            concat!(
+                "{\"year\":2015,\"day\":1,\"title\":\"Not Quite Lisp\",\"part\":1,",
+                "\"answer\":\"-1\",\"duration\":1.5,\"parse_duration\":null,",
+                "\"status\":\"mismatch\",\"expected\":\"say \\\"hi\\\"\",\"error\":null}"
+            )
+        );
+        assert_eq!(
+            record.tsv(),
+            "2015\t1\tNot Quite Lisp\t1\t-1\t1.500000000\t\tmismatch\tsay \"hi\"\t"
+        );
+        assert_eq!(record.tsv().split('\t').count(), Record::TSV_HEADER.len());
+        let mut record = Record::new(problem);
+        record.error = Some(Failure::Other("solve", "a\tb".into()));
+        assert!(record
+            .json()
+            .ends_with(",\"error\":{\"kind\":\"solve\",\"message\":\"a\\tb\"}}"));
+        assert!(record.tsv().ends_with("\t\t\t\t\t\ta\\tb"));
+    }
+}
@@ -0,0 +1,295 @@
+use std::ops::RangeInclusive;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{Answer, Error, ParseOptions, Problem, SolveContext, SolveError};
+
+pub struct Report {
+    problem: &'static Problem,
+    algo: Option<&'static str>,
+    parse: Duration,
+    parts: Vec<PartReport>,
+}
+
+impl Report {
+    pub fn problem(&self) -> &'static Problem {
+        self.problem
+    }
+    pub fn algo(&self) -> Option<&'static str> {
+        self.algo
+    }
+    pub fn parse_time(&self) -> Duration {
+        self.parse
+    }
+    pub fn parts(&self) -> &[PartReport] {
+        &self.parts
+    }
+    pub fn part(&self, part: u8) -> Option<&PartReport> {
+        self.parts.iter().find(|report| report.part == part)
+    }
+    pub fn total_time(&self) -> Duration {
+        self.parts
+            .iter()
+            .map(|report| report.time)
+            .sum::<Duration>()
+            + self.parse
+    }
+}
+
+pub struct PartReport {
+    part: u8,
+    answer: Result<Answer, SolveError>,
+    time: Duration,
+}
+
+impl PartReport {
+    pub fn part(&self) -> u8 {
+        self.part
+    }
+    pub fn answer(&self) -> Result<&Answer, &SolveError> {
+        self.answer.as_ref()
+    }
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+pub fn run(problem: &'static Problem, input: &str) -> Result<Report, Error> {
+    let parts = 1..=problem.parts();
+    run_parts(
+        problem,
+        input,
+        parts,
+        &ParseOptions::new(),
+        &SolveContext::new(),
+    )
+}
+
+pub fn run_parts(
+    problem: &'static Problem,
+    input: &str,
+    parts: RangeInclusive<u8>,
+    options: &ParseOptions,
+    ctx: &SolveContext,
+) -> Result<Report, Error> {
+    let start = Instant::now();
+    let mut solver = problem.parse_with(input, options)?;
+    let parse = start.elapsed();
+    let parts = parts
+        .map(|part| timed(part, || solver.solve_with(part, &ctx.start())))
+        .collect();
+    Ok(Report {
+        problem,
+        algo: algo_name(problem, options),
+        parse,
+        parts,
+    })
+}
+
+pub fn run_shared(
+    problem: &'static Problem,
+    input: &str,
+    parts: RangeInclusive<u8>,
+    options: &ParseOptions,
+    ctx: &SolveContext,
+) -> Result<Report, Error> {
+    let start = Instant::now();
+    let solver = problem.parse_with(input, options)?;
+    let parse = start.elapsed();
+    let parts = match solver.into_shared() {
+        Ok(shared) => thread::scope(|scope| {
+            let shared = &shared;
+            let handles: Vec<_> = parts
+                .map(|part| {
+                    scope.spawn(move || timed(part, || shared.solve_with(part, &ctx.start())))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|err| panic::resume_unwind(err))
+                })
+                .collect()
+        }),
+        Err(mut solver) => parts
+            .map(|part| timed(part, || solver.solve_with(part, &ctx.start())))
+            .collect(),
+    };
+    Ok(Report {
+        problem,
+        algo: algo_name(problem, options),
+        parse,
+        parts,
+    })
+}
+
+pub fn run_pool(
+    jobs: &[(&'static Problem, &str)],
+    threads: usize,
+    options: &ParseOptions,
+    ctx: &SolveContext,
+) -> Vec<Result<Report, Error>> {
+    let next = AtomicUsize::new(0);
+    let results: Vec<_> = jobs.iter().map(|_| Mutex::new(None)).collect();
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&(problem, input)) = jobs.get(i) else {
+                    break;
+                };
+                let report = run_parts(problem, input, 1..=problem.parts(), options, ctx);
+                *results[i].lock().unwrap() = Some(report);
+            });
+        }
+    });
+    results
+        .into_iter()
+        .map(|result| result.into_inner().unwrap().unwrap())
+        .collect()
+}
+
+pub fn compare_algos(
+    report: &Report,
+    input: &str,
+    options: &ParseOptions,
+    ctx: &SolveContext,
+) -> Result<Vec<(&'static str, PartReport)>, Error> {
+    let problem = report.problem;
+    let parts = match (report.parts.first(), report.parts.last()) {
+        (Some(first), Some(last)) => first.part..=last.part,
+        _ => return Ok(Vec::new()),
+    };
+    let mut found = Vec::new();
+    for algo in problem.algos() {
+        if report.algo == Some(algo.name()) {
+            continue;
+        }
+        let options = options.algo(algo.name());
+        let other = run_parts(problem, input, parts.clone(), &options, ctx)?;
+        for (part, expect) in other.parts.into_iter().zip(&report.parts) {
+            if part.answer != expect.answer {
+                found.push((algo.name(), part));
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn algo_name(problem: &Problem, options: &ParseOptions) -> Option<&'static str> {
+    options
+        .algo
+        .or(problem.default_algo().map(|algo| algo.name()))
+}
+
+fn timed(part: u8, solve: impl FnOnce() -> Result<Answer, SolveError>) -> PartReport {
+    let start = Instant::now();
+    let answer = solve();
+    let time = start.elapsed();
+    PartReport { part, answer, time }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run() {
+        let problem = &crate::PROBLEMS[0];
+        let ctx = SolveContext::new();
+        let report = super::run(problem, "())").unwrap();
+        assert_eq!(report.problem().day(), 1);
+        assert_eq!(report.parts().len(), 2);
+        assert_eq!(report.part(1).unwrap().answer(), Ok(&(-1i64).into()));
+        assert_eq!(report.part(2).unwrap().answer(), Ok(&3u64.into()));
+        let report = run_parts(problem, "())", 2..=3, &ParseOptions::new(), &ctx).unwrap();
+        assert!(report.part(1).is_none());
+        let err = SolveError::PartNotFound(3);
+        assert_eq!(report.part(3).unwrap().answer(), Err(&err));
+        assert!(super::run(problem, "(x").is_err());
+        let strict = ParseOptions::new().normalize(crate::Normalize::NONE);
+        assert!(run_parts(problem, "())\n", 1..=2, &strict, &ctx).is_err());
+    }
+
+    #[test]
+    fn run_shared() {
+        let problem = crate::find(2015, 3).unwrap();
+        let (options, ctx) = (ParseOptions::new(), SolveContext::new());
+        let report = super::run_shared(problem, "^v^v^v^v^v", 1..=3, &options, &ctx).unwrap();
+        assert_eq!(report.part(1).unwrap().answer(), Ok(&2u64.into()));
+        assert_eq!(report.part(2).unwrap().answer(), Ok(&11u64.into()));
+        let err = SolveError::PartNotFound(3);
+        assert_eq!(report.part(3).unwrap().answer(), Err(&err));
+        let problem = &crate::PROBLEMS[0];
+        let report = super::run_shared(problem, "())", 1..=2, &options, &ctx).unwrap();
+        assert_eq!(report.part(2).unwrap().answer(), Ok(&3u64.into()));
+    }
+
+    #[test]
+    fn run_pool() {
+        let jobs = [
+            (&crate::PROBLEMS[0], "())"),
+            (&crate::PROBLEMS[1], "2x3x4"),
+            (&crate::PROBLEMS[0], "(x"),
+            (&crate::PROBLEMS[2], "^>v<"),
+        ];
+        let (options, ctx) = (ParseOptions::new(), SolveContext::new());
+        let results = super::run_pool(&jobs, 3, &options, &ctx);
+        assert_eq!(results.len(), 4);
+        let day = |i: usize| results[i].as_ref().unwrap().problem().day();
+        assert_eq!((day(0), day(1), day(3)), (1, 2, 3));
+        assert!(results[2].is_err());
+        let answer = results[1].as_ref().unwrap().part(1).unwrap().answer();
+        assert_eq!(answer, Ok(&58u64.into()));
+        assert!(super::run_pool(&[], 4, &options, &ctx).is_empty());
+    }
+
+    #[test]
+    fn timeout() {
+        let problem = crate::find(2015, 4).unwrap();
+        let ctx = SolveContext::new().timeout(Duration::from_millis(10));
+        let options = ParseOptions::new();
+        let report = super::run_shared(problem, "abcdef", 1..=2, &options, &ctx).unwrap();
+        for part in report.parts() {
+            assert_eq!(part.answer(), Err(&SolveError::Timeout));
+        }
+        let ctx = SolveContext::new();
+        ctx.cancel();
+        let report = run_parts(problem, "abcdef", 1..=2, &options, &ctx).unwrap();
+        assert_eq!(
+            report.part(1).unwrap().answer(),
+            Err(&SolveError::Cancelled)
+        );
+    }
+
+    #[test]
+    fn compare_algos() {
+        let problem = crate::find(2015, 3).unwrap();
+        let (options, ctx) = (ParseOptions::new(), SolveContext::new());
+        let report = run_parts(problem, "^>v<", 1..=2, &options, &ctx).unwrap();
+        assert_eq!(report.algo(), Some("sort"));
+        let found = super::compare_algos(&report, "^>v<", &options, &ctx).unwrap();
+        assert!(found.is_empty());
+        let report = run_parts(problem, "^v^v", 1..=2, &options, &ctx).unwrap();
+        let found = super::compare_algos(&report, "^>v<", &options, &ctx).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].0, "hashset");
+        assert_eq!(found[0].1.answer(), Ok(&4u64.into()));
+        let hashset = ParseOptions::new().algo("hashset");
+        let report = run_parts(problem, "^v^v", 1..=2, &hashset, &ctx).unwrap();
+        assert_eq!(report.algo(), Some("hashset"));
+        let found = super::compare_algos(&report, "^>v<", &options, &ctx).unwrap();
+        let algos: Vec<_> = found.iter().map(|(algo, _)| *algo).collect();
+        assert_eq!(algos, ["sort", "sort"]);
+        let report = super::run(&crate::PROBLEMS[0], "())").unwrap();
+        assert_eq!(report.algo(), None);
+        assert!(super::compare_algos(&report, "())", &options, &ctx)
+            .unwrap()
+            .is_empty());
+    }
+}
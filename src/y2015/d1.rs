@@ -1,11 +1,20 @@
-use super::super::{ParseError, Problem, SolveError, Solver};
+use std::sync::Arc;
+
+use super::super::{
+    Answer, ErrorKind, InputSummary, Normalize, ParseError, ParseErrors, Problem, SharedSolver,
+    SolveError, Solver,
+};
 
 pub const PROBLEM: Problem = Problem {
     year: 2015,
     day: 1,
     title: "Not Quite Lisp",
     parts: 2,
-    parse: |input| Ok(Box::new(Day1::new(input)?)),
+    normalize: Normalize::ALL,
+    parse: |input, errors| Some(Box::new(Day1::new(input, errors)?)),
+    stream: None,
+    params: &[],
+    algos: &[],
 };
 
 struct Day1 {
@@ -15,54 +24,56 @@ struct Day1 {
 }
 
 impl Day1 {
-    fn new<'a>(input: &'a str) -> Result<Self, ParseError<'a>> {
+    fn new<'a>(input: &'a str, errors: &mut ParseErrors<'a>) -> Option<Self> {
         let mut res = Day1 {
             open: 0,
             close: 0,
             pos: 0,
         };
-        for (i, ch) in input.chars().enumerate() {
+        for (i, (j, ch)) in input.char_indices().enumerate() {
             match ch {
                 '(' => res.open += 1,
                 ')' => res.close += 1,
                 _ => {
-                    return Err(ParseError {
-                        msg: "Invalid character",
-                        line: 1,
-                        pos: i + 1,
-                        source: None,
-                        arg: &input[i..=i],
-                    });
+                    let span = j..j + ch.len_utf8();
+                    if !errors.push(ParseError::new(ErrorKind::InvalidCharacter, input, span)) {
+                        return None;
+                    }
                 }
             }
             if res.pos == 0 && res.open + 1 == res.close {
                 res.pos = i + 1;
             }
         }
-        Ok(res)
+        errors.is_empty().then_some(res)
     }
 
-    fn part1(&mut self) -> String {
-        if self.open >= self.close {
-            (self.open - self.close).to_string()
-        } else {
-            format!("-{}", self.close - self.open)
-        }
+    fn part1(&mut self) -> Answer {
+        (self.open as i64 - self.close as i64).into()
     }
 
-    fn part2(&mut self) -> String {
-        self.pos.to_string()
+    fn part2(&mut self) -> Result<Answer, SolveError> {
+        match self.pos {
+            0 => Err(SolveError::NoSolution),
+            pos => Ok(pos.into()),
+        }
     }
 }
 
 impl Solver for Day1 {
-    fn solve(&mut self, part: u8) -> Result<String, SolveError> {
+    fn solve(&mut self, part: u8) -> Result<Answer, SolveError> {
         match part {
             1 => Ok(self.part1()),
-            2 => Ok(self.part2()),
+            2 => self.part2(),
             _ => Err(SolveError::PartNotFound(part)),
         }
     }
+    fn summary(&self) -> InputSummary {
+        InputSummary::new(self.open + self.close)
+    }
+    fn into_shared(self: Box<Self>) -> Result<Arc<dyn SharedSolver>, Box<dyn Solver>> {
+        Err(self)
+    }
 }
 
 #[cfg(test)]
@@ -70,16 +81,15 @@ mod tests {
     use super::*;
 
     fn test(part: u8, input: &str, expect: &str) {
-        let mut solver = Day1::new(input).unwrap();
+        let mut solver = PROBLEM.parse(input).unwrap();
         assert_eq!(solver.solve(part), Ok(expect.into()));
     }
 
     #[test]
     fn bad() {
-        for input in [")\n"] {
-            if let Ok(_) = Day1::new(input) {
-                panic!();
-            }
+        for (input, pos) in [(")\n\n", 2), ("(\r)", 2), ("(a)", 2)] {
+            let err = PROBLEM.parse(input).err().unwrap();
+            assert_eq!((err.kind(), err.pos()), (ErrorKind::InvalidCharacter, pos));
         }
     }
 
@@ -91,6 +101,7 @@ mod tests {
         test(1, "(()(()(", "3");
         test(1, "))(((((", "3");
         test(1, "())", "-1");
+        test(1, "\u{feff}())\r\n", "-1");
         test(1, "))(", "-1");
         test(1, ")))", "-3");
         test(1, ")())())", "-3");
@@ -100,5 +111,7 @@ mod tests {
     fn part2() {
         test(2, ")", "1");
         test(2, "()())", "5");
+        let mut solver = PROBLEM.parse("(()").unwrap();
+        assert_eq!(solver.solve(2), Err(SolveError::NoSolution));
     }
 }
@@ -1,82 +1,102 @@
-use super::super::{ParseError, Problem, SolveError, Solver};
+use std::sync::Arc;
+
+use super::super::{
+    offset, parse_lines, stream_lines, Answer, ErrorKind, InputSummary, LineParser, Normalize,
+    ParseError, Problem, SharedSolver, SolveError, Solver,
+};
 
 pub const PROBLEM: Problem = Problem {
     year: 2015,
     day: 2,
     title: "I Was Told There Would Be No Math",
     parts: 2,
-    parse: |input| Ok(Box::new(Day2::new(input)?)),
+    normalize: Normalize::ALL,
+    parse: parse_lines::<Day2>,
+    stream: Some(stream_lines::<Day2>),
+    params: &[],
+    algos: &[],
 };
 
+#[derive(Default)]
 struct Day2 {
     paper: u128,
     ribbon: u128,
+    boxes: usize,
+    range: Option<(u32, u32)>,
 }
 
 impl Day2 {
-    fn new<'a>(input: &'a str) -> Result<Self, ParseError<'a>> {
-        let mut res = Day2 {
-            paper: 0,
-            ribbon: 0,
-        };
-        for (i, line) in input.lines().enumerate() {
-            const SPLIT: &str = "x";
-            let mut pos = 0usize;
-            let mut values = [0u32; 3];
-            let mut parts = line.splitn(values.len(), SPLIT);
-            for value in &mut values {
-                pos += 1;
-                if let Some(part) = parts.next() {
-                    *value = match part.parse() {
-                        Ok(value) => value,
-                        Err(err) => {
-                            return Err(ParseError {
-                                msg: "",
-                                line: i + 1,
-                                pos,
-                                source: Some(Box::new(err)),
-                                arg: part,
-                            })
-                        }
-                    };
-                    pos += part.chars().count();
-                } else {
-                    return Err(ParseError {
-                        msg: "Expected data",
-                        line: i + 1,
-                        pos,
-                        source: None,
-                        arg: "",
-                    });
-                }
-            }
-            values.sort_unstable();
-            let x = values[0] as u128;
-            let y = values[1] as u128;
-            let z = values[2] as u128;
-            res.paper += 3 * x * y + 2 * (x + y) * z;
-            res.ribbon += 2 * (x + y) + x * y * z;
-        }
-        Ok(res)
+    fn part1(&mut self) -> Answer {
+        self.paper.into()
     }
 
-    fn part1(&mut self) -> String {
-        self.paper.to_string()
+    fn part2(&mut self) -> Answer {
+        self.ribbon.into()
+    }
+}
+
+fn dimensions(line: &str) -> Result<[u32; 3], ParseError<'_>> {
+    const SPLIT: &str = "x";
+    let mut values = [0u32; 3];
+    let mut parts = line.split(SPLIT);
+    for value in &mut values {
+        let Some(part) = parts.next() else {
+            let end = line.len();
+            return Err(ParseError::new(ErrorKind::UnexpectedEnd, line, end..end));
+        };
+        let start = offset(line, part);
+        *value = part.parse().map_err(|err| {
+            ParseError::new(ErrorKind::NumberFormat, line, start..start + part.len())
+                .with_source(err)
+        })?;
+    }
+    if let Some(part) = parts.next() {
+        let start = offset(line, part) - SPLIT.len();
+        return Err(ParseError::new(
+            ErrorKind::TrailingData,
+            line,
+            start..line.len(),
+        ));
     }
+    Ok(values)
+}
 
-    fn part2(&mut self) -> String {
-        self.ribbon.to_string()
+impl LineParser for Day2 {
+    fn line<'a>(&mut self, line: &'a str) -> Result<(), ParseError<'a>> {
+        let mut values = dimensions(line)?;
+        values.sort_unstable();
+        self.boxes += 1;
+        self.range = match self.range {
+            Some((min, max)) => Some((min.min(values[0]), max.max(values[2]))),
+            None => Some((values[0], values[2])),
+        };
+        let x = values[0] as u128;
+        let y = values[1] as u128;
+        let z = values[2] as u128;
+        self.paper += 3 * x * y + 2 * (x + y) * z;
+        self.ribbon += 2 * (x + y) + x * y * z;
+        Ok(())
     }
 }
 
 impl Solver for Day2 {
-    fn solve(&mut self, part: u8) -> Result<String, SolveError> {
+    fn solve(&mut self, part: u8) -> Result<Answer, SolveError> {
         match part {
             1 => Ok(self.part1()),
             2 => Ok(self.part2()),
             _ => Err(SolveError::PartNotFound(part)),
         }
     }
+    fn summary(&self) -> InputSummary {
+        let summary = InputSummary::new(self.boxes);
+        match self.range {
+            Some((min, max)) => summary.with_range(min.into(), max.into()),
+            None => summary,
+        }
+    }
+    fn into_shared(self: Box<Self>) -> Result<Arc<dyn SharedSolver>, Box<dyn Solver>> {
+        Err(self)
+    }
 }
 
 #[cfg(test)]
@@ -84,17 +104,23 @@ mod tests {
     use super::*;
 
     fn test(part: u8, input: &str, expect: &str) {
-        let mut solver = Day2::new(input).unwrap();
+        let mut solver = PROBLEM.parse(input).unwrap();
         assert_eq!(solver.solve(part), Ok(expect.into()));
     }
 
     #[test]
     fn bad() {
-        for input in ["2", "2x3", "2x3xZ", "2x3x4x5"] {
-            if let Ok(_) = Day2::new(input) {
-                panic!();
-            }
+        for (input, kind, pos) in [
+            ("2", ErrorKind::UnexpectedEnd, 2),
+            ("2x3", ErrorKind::UnexpectedEnd, 4),
+            ("2x3xZ", ErrorKind::NumberFormat, 5),
+            ("2x3x4x5", ErrorKind::TrailingData, 6),
+        ] {
+            let err = PROBLEM.parse(input).err().unwrap();
+            assert_eq!((err.kind(), err.pos()), (kind, pos), "{input}");
         }
+        let err = PROBLEM.parse("2x3x4x5").err().unwrap();
+        assert_eq!(err.arg(), "x5");
     }
 
     #[test]
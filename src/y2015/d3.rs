@@ -1,19 +1,40 @@
-use super::super::{ParseError, Problem, SolveError, Solver};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::super::{
+    Algo, Answer, ErrorKind, InputSummary, Normalize, Param, ParseError, ParseErrors, Problem,
+    SharedSolver, SolveContext, SolveError, Solver,
+};
 
 pub const PROBLEM: Problem = Problem {
     year: 2015,
     day: 3,
     title: "Perfectly Spherical Houses in a Vacuum",
     parts: 2,
-    parse: |input| Ok(Box::new(Day3::new(input)?)),
+    normalize: Normalize::ALL,
+    parse: ALGOS[0].parse,
+    stream: None,
+    params: &[SANTAS],
+    algos: &ALGOS,
 };
 
+const ALGOS: [Algo; 2] = [
+    Algo::new("sort", |input, errors| {
+        Some(Box::new(Day3::new(input, errors, sort)?))
+    }),
+    Algo::new("hashset", |input, errors| {
+        Some(Box::new(Day3::new(input, errors, hashset)?))
+    }),
+];
+
+const SANTAS: Param = Param::new("santas", 2, 1, 1000);
+
 enum Move {
     Horizontal(i8),
     Vertical(i8),
 }
 
-#[derive(Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
 struct Point {
     x: isize,
     y: isize,
@@ -21,61 +42,94 @@ struct Point {
 
 struct Day3 {
     moves: Vec<Move>,
+    count: fn(Vec<Point>) -> usize,
 }
 
 impl Day3 {
-    fn new<'a>(input: &'a str) -> Result<Self, ParseError<'a>> {
+    fn new<'a>(
+        input: &'a str,
+        errors: &mut ParseErrors<'a>,
+        count: fn(Vec<Point>) -> usize,
+    ) -> Option<Self> {
         let mut moves = Vec::<Move>::with_capacity(input.len());
-        for (i, ch) in input.chars().enumerate() {
+        for (j, ch) in input.char_indices() {
             match ch {
                 '<' => moves.push(Move::Horizontal(-1)),
                 '>' => moves.push(Move::Horizontal(1)),
                 '^' => moves.push(Move::Vertical(-1)),
                 'v' => moves.push(Move::Vertical(1)),
                 _ => {
-                    return Err(ParseError {
-                        msg: "Invalid character",
-                        line: 1,
-                        pos: i + 1,
-                        source: None,
-                        arg: &input[i..=i],
-                    });
+                    let span = j..j + ch.len_utf8();
+                    if !errors.push(ParseError::new(ErrorKind::InvalidCharacter, input, span)) {
+                        return None;
+                    }
                 }
             }
         }
-        Ok(Day3 { moves })
+        errors.is_empty().then_some(Day3 { moves, count })
     }
 
-    fn _solve(&mut self, p: usize) -> String {
+    fn _solve(&self, p: usize) -> Answer {
+        (self.count)(self.visit(p)).into()
+    }
+
+    fn visit(&self, p: usize) -> Vec<Point> {
         let mut points = Vec::<Point>::with_capacity(self.moves.len() + 1);
         points.push(Point { x: 0, y: 0 });
         for (i, m) in self.moves.iter().enumerate() {
-            let Point { mut x, mut y } = points[if i < p { 0 } else { i - p }];
+            let Point { mut x, mut y } = points[i.saturating_sub(p)];
             match *m {
                 Move::Horizontal(d) => x += d as isize,
                 Move::Vertical(d) => y += d as isize,
             }
             points.push(Point { x, y });
         }
-        points.sort_unstable();
-        let mut points = points.into_iter();
-        let mut prev = points.next().unwrap();
-        let mut result: usize = 1;
-        for point in points {
-            if point != prev {
-                result += 1;
-                prev = point;
-            }
+        points
+    }
+}
+
+fn sort(mut points: Vec<Point>) -> usize {
+    points.sort_unstable();
+    let mut points = points.into_iter();
+    let mut prev = points.next().unwrap();
+    let mut result: usize = 1;
+    for point in points {
+        if point != prev {
+            result += 1;
+            prev = point;
         }
-        result.to_string()
     }
+    result
+}
+
+fn hashset(points: Vec<Point>) -> usize {
+    points.into_iter().collect::<HashSet<_>>().len()
 }
 
 impl Solver for Day3 {
-    fn solve(&mut self, part: u8) -> Result<String, SolveError> {
+    fn solve(&mut self, part: u8) -> Result<Answer, SolveError> {
+        SharedSolver::solve(self, part)
+    }
+    fn solve_with(&mut self, part: u8, ctx: &SolveContext) -> Result<Answer, SolveError> {
+        SharedSolver::solve_with(self, part, ctx)
+    }
+    fn summary(&self) -> InputSummary {
+        InputSummary::new(self.moves.len())
+    }
+    fn into_shared(self: Box<Self>) -> Result<Arc<dyn SharedSolver>, Box<dyn Solver>> {
+        Ok(Arc::new(*self))
+    }
+}
+
+impl SharedSolver for Day3 {
+    fn solve(&self, part: u8) -> Result<Answer, SolveError> {
+        self.solve_with(part, &SolveContext::new())
+    }
+    fn solve_with(&self, part: u8, ctx: &SolveContext) -> Result<Answer, SolveError> {
+        ctx.check()?;
         match part {
             1 => Ok(self._solve(0)),
-            2 => Ok(self._solve(1)),
+            2 => Ok(self._solve(SANTAS.value(ctx)? as usize - 1)),
             _ => Err(SolveError::PartNotFound(part)),
         }
     }
@@ -86,22 +140,40 @@ mod tests {
     use super::*;
 
     fn test(part: u8, input: &str, expect: &str) {
-        let mut solver = Day3::new(input).unwrap();
+        let mut solver = PROBLEM.parse(input).unwrap();
         assert_eq!(solver.solve(part), Ok(expect.into()));
     }
 
     #[test]
     fn bad() {
-        for input in [">\n"] {
-            if let Ok(_) = Day3::new(input) {
-                panic!();
-            }
+        for (input, pos) in [(">\n\n", 2), ("^x", 2)] {
+            let err = PROBLEM.parse(input).err().unwrap();
+            assert_eq!((err.kind(), err.pos()), (ErrorKind::InvalidCharacter, pos));
+        }
+    }
+
+    #[test]
+    fn params() {
+        let mut solver = PROBLEM.parse("^v^v").unwrap();
+        for (santas, expect) in [(1, Ok(2u64.into())), (3, Ok(3u64.into()))] {
+            let mut params = crate::Params::new();
+            params.set("santas", santas);
+            let ctx = SolveContext::new().params(params);
+            assert_eq!(solver.solve_with(2, &ctx), expect);
+        }
+        for santas in [0, 1001] {
+            let mut params = crate::Params::new();
+            params.set("santas", santas);
+            let ctx = SolveContext::new().params(params);
+            let err = solver.solve_with(2, &ctx).unwrap_err();
+            assert!(matches!(err, SolveError::InvalidInput { .. }));
         }
     }
 
     #[test]
     fn part1() {
         test(1, ">", "2");
+        test(1, ">\n", "2");
         test(1, "^>v<", "4");
         test(1, "^v^v^v^v^v", "2");
     }
@@ -1,60 +1,90 @@
-use super::super::{ParseError, Problem, SolveError, Solver};
+use std::sync::Arc;
+
+use super::super::{
+    Answer, ErrorKind, InputSummary, Normalize, Param, ParseError, ParseErrors, Problem,
+    SharedSolver, SolveContext, SolveError, Solver,
+};
 
 pub const PROBLEM: Problem = Problem {
     year: 2015,
     day: 4,
     title: "The Ideal Stocking Stuffer",
     parts: 2,
-    parse: |input| Ok(Box::new(Day4::new(input)?)),
+    normalize: Normalize::ALL,
+    parse: |input, errors| Some(Box::new(Day4::new(input, errors)?)),
+    stream: None,
+    params: &[ZEROS],
+    algos: &[],
 };
 
+const ZEROS: Param = Param::new("zeros", 6, 1, 8);
+
 struct Day4 {
     input: String,
 }
 
 impl Day4 {
-    fn new<'a>(input: &'a str) -> Result<Self, ParseError<'a>> {
+    fn new<'a>(input: &'a str, errors: &mut ParseErrors<'a>) -> Option<Self> {
         if input.len() > 20 {
-            return Err(ParseError {
-                msg: "Too long",
-                line: 1,
-                pos: 1,
-                source: None,
-                arg: input,
-            });
+            errors.push(ParseError::new(ErrorKind::TooLong, input, 0..input.len()));
+            return None;
         }
-        Ok(Day4 {
+        Some(Day4 {
             input: input.to_string(),
         })
     }
 
-    fn mask(&mut self, m: u32) -> String {
+    fn mask(&self, m: u32, ctx: &SolveContext) -> Result<Answer, SolveError> {
+        let total = 1u64 << m.count_ones();
         let mut i: usize = 0;
         loop {
-            i = i.wrapping_add(1);
-            if i == 0 {
-                panic!("Overflow!");
+            if i.is_multiple_of(4096) {
+                ctx.check()?;
+                ctx.report(i as u64, Some(total));
             }
+            i = i.checked_add(1).ok_or(SolveError::Overflow)?;
             let text = format!("{}{}", self.input, i);
-            let a = md5a(&text.into_bytes());
+            let a = md5a(&text.into_bytes())?;
             if (a & m) == 0 {
-                return i.to_string();
+                return Ok(i.into());
             }
         }
     }
 }
 
 impl Solver for Day4 {
-    fn solve(&mut self, part: u8) -> Result<String, SolveError> {
+    fn solve(&mut self, part: u8) -> Result<Answer, SolveError> {
+        SharedSolver::solve(self, part)
+    }
+    fn solve_with(&mut self, part: u8, ctx: &SolveContext) -> Result<Answer, SolveError> {
+        SharedSolver::solve_with(self, part, ctx)
+    }
+    fn summary(&self) -> InputSummary {
+        InputSummary::new(1)
+    }
+    fn into_shared(self: Box<Self>) -> Result<Arc<dyn SharedSolver>, Box<dyn Solver>> {
+        Ok(Arc::new(*self))
+    }
+}
+
+impl SharedSolver for Day4 {
+    fn solve(&self, part: u8) -> Result<Answer, SolveError> {
+        self.solve_with(part, &SolveContext::new())
+    }
+    fn solve_with(&self, part: u8, ctx: &SolveContext) -> Result<Answer, SolveError> {
         match part {
-            1 => Ok(self.mask(0xf0ffff)),
-            2 => Ok(self.mask(0xffffff)),
+            1 => self.mask(zeros(5), ctx),
+            2 => self.mask(zeros(ZEROS.value(ctx)?), ctx),
             _ => Err(SolveError::PartNotFound(part)),
         }
     }
 }
 
-fn md5a(input: &[u8]) -> u32 {
+fn zeros(n: u64) -> u32 {
+    (0..n).fold(0, |m, k| m | 0xf << (8 * (k / 2) + 4 * (1 - k % 2)))
+}
+
+fn md5a(input: &[u8]) -> Result<u32, SolveError> {
     const S: [u8; 64] = [
         7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5,
         9, 14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10,
@@ -75,7 +105,9 @@ fn md5a(input: &[u8]) -> u32 {
     const I: (u32, u32, u32, u32) = (0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476);
     let len = input.len();
     if len >= 56 {
-        panic!("Too big");
+        return Err(SolveError::InvalidInput {
+            reason: "Too big for one MD5 block",
+        });
     }
     let len: u32 = len as u32;
     let mut m: Vec<u8> = input.into();
@@ -109,9 +141,9 @@ fn md5a(input: &[u8]) -> u32 {
         d = c;
         c = b;
         let y = S[i];
-        b = b.wrapping_add((x << y) | (x >> (32 - y)));
+        b = b.wrapping_add(x.rotate_left(y as u32));
     }
-    a.wrapping_add(I.0)
+    Ok(a.wrapping_add(I.0))
 }
 
 #[cfg(test)]
@@ -119,23 +151,67 @@ mod tests {
     use super::*;
 
     fn test(part: u8, input: &str, expect: &str) {
-        let mut solver = Day4::new(input).unwrap();
+        let mut solver = PROBLEM.parse(input).unwrap();
         assert_eq!(solver.solve(part), Ok(expect.into()));
     }
 
     #[test]
     fn bad() {
-        for input in ["123456789012345678901234567890"] {
-            if let Ok(_) = Day4::new(input) {
-                panic!();
-            }
+        for input in ["123456789012345678901", "123456789012345678901234567890"] {
+            let err = PROBLEM.parse(input).err().unwrap();
+            assert_eq!(err.kind(), ErrorKind::TooLong);
         }
     }
 
     #[test]
     fn utils() {
-        assert_eq!(md5a("abcdef609043".as_bytes()), 0xdb010000);
-        assert_eq!(md5a("pqrstuv1048970".as_bytes()), 0x13060000);
+        assert_eq!(md5a("abcdef609043".as_bytes()), Ok(0xdb010000));
+        assert_eq!(md5a("pqrstuv1048970".as_bytes()), Ok(0x13060000));
+        assert!(md5a(&[b'x'; 56]).is_err());
+        assert_eq!(zeros(5), 0xf0ffff);
+        assert_eq!(zeros(6), 0xffffff);
+        assert_eq!(zeros(8), 0xffffffff);
+    }
+
+    #[test]
+    fn cancel() {
+        let mut solver = PROBLEM.parse("abcdef").unwrap();
+        let ctx = SolveContext::new();
+        ctx.cancel();
+        assert_eq!(solver.solve_with(2, &ctx), Err(SolveError::Cancelled));
+        let ctx = SolveContext::new().timeout(std::time::Duration::from_millis(10));
+        assert_eq!(solver.solve_with(2, &ctx), Err(SolveError::Timeout));
+        assert_eq!(solver.solve_with(2, &ctx.start()), Err(SolveError::Timeout));
+    }
+
+    #[test]
+    fn progress() {
+        let ctx = SolveContext::new();
+        let cancel = ctx.clone();
+        let ctx = ctx.progress(move |progress| {
+            assert_eq!(progress.total(), Some(1 << 20));
+            if progress.done() >= 8192 {
+                cancel.cancel();
+            }
+        });
+        let mut solver = PROBLEM.parse("abcdef").unwrap();
+        assert_eq!(solver.solve_with(1, &ctx), Err(SolveError::Cancelled));
+    }
+
+    #[test]
+    fn params() {
+        let mut params = crate::Params::new();
+        params.set("zeros", 3);
+        let ctx = SolveContext::new().params(params);
+        let mut solver = PROBLEM.parse("abcdef").unwrap();
+        assert_eq!(solver.solve_with(2, &ctx), Ok(3337u64.into()));
+        for zeros in [0, 9, 64] {
+            let mut params = crate::Params::new();
+            params.set("zeros", zeros);
+            let ctx = SolveContext::new().params(params);
+            let err = solver.solve_with(2, &ctx).unwrap_err();
+            assert!(matches!(err, SolveError::InvalidInput { .. }));
+        }
     }
 
     #[test]
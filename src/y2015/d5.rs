@@ -1,53 +1,90 @@
-use super::super::{ParseError, Problem, SolveError, Solver};
+use std::sync::Arc;
+
+use super::super::{
+    parse_lines, stream_lines, Answer, ErrorKind, InputSummary, LineParser, Normalize, ParseError,
+    Problem, SharedSolver, SolveError, Solver,
+};
 
 pub const PROBLEM: Problem = Problem {
     year: 2015,
     day: 5,
     title: "Doesn't He Have Intern-Elves For This?",
     parts: 2,
-    parse: |input| Ok(Box::new(Day5::new(input)?)),
+    normalize: Normalize::ALL,
+    parse: parse_lines::<Day5>,
+    stream: Some(stream_lines::<Day5>),
+    params: &[],
+    algos: &[],
 };
 
+#[derive(Default)]
 struct Day5 {
-    input: Vec<String>,
+    nice: usize,
+    nicer: usize,
+    strings: usize,
+    range: Option<(usize, usize)>,
 }
 
 impl Day5 {
-    fn new<'a>(input: &'a str) -> Result<Self, ParseError<'a>> {
-        Ok(Day5 {
-            input: input.lines().map(|line| line.to_string()).collect(),
-        })
+    fn part1(&mut self) -> Answer {
+        self.nice.into()
     }
 
-    fn part1(&mut self) -> String {
-        let mut result: usize = 0;
-        for text in &self.input {
-            if aei(text) >= 3 && xx(text) > 0 && !bad(text) {
-                result += 1;
-            }
-        }
-        result.to_string()
+    fn part2(&mut self) -> Answer {
+        self.nicer.into()
     }
+}
 
-    fn part2(&mut self) -> String {
-        let mut result: usize = 0;
-        for text in &self.input {
-            if xyxy(text) > 0 && xyx(text) > 0 {
-                result += 1;
-            }
+impl LineParser for Day5 {
+    fn line<'a>(&mut self, text: &'a str) -> Result<(), ParseError<'a>> {
+        if text.is_empty() {
+            return Err(
+                ParseError::new(ErrorKind::UnexpectedEnd, text, 0..0).with_msg("Expected letters")
+            );
+        }
+        if let Some(i) = text.find(|ch: char| !ch.is_ascii_lowercase()) {
+            let len = text[i..].chars().next().map_or(1, char::len_utf8);
+            return Err(ParseError::new(
+                ErrorKind::InvalidCharacter,
+                text,
+                i..i + len,
+            ));
+        }
+        let len = text.len();
+        self.strings += 1;
+        self.range = match self.range {
+            Some((min, max)) => Some((min.min(len), max.max(len))),
+            None => Some((len, len)),
+        };
+        if aei(text) >= 3 && xx(text) > 0 && !bad(text) {
+            self.nice += 1;
+        }
+        if xyxy(text) > 0 && xyx(text) > 0 {
+            self.nicer += 1;
         }
-        result.to_string()
+        Ok(())
     }
 }
 
 impl Solver for Day5 {
-    fn solve(&mut self, part: u8) -> Result<String, SolveError> {
+    fn solve(&mut self, part: u8) -> Result<Answer, SolveError> {
         match part {
             1 => Ok(self.part1()),
             2 => Ok(self.part2()),
             _ => Err(SolveError::PartNotFound(part)),
         }
     }
+
+    fn summary(&self) -> InputSummary {
+        let summary = InputSummary::new(self.strings);
+        match self.range {
+            Some((min, max)) => summary.with_range(min as u64, max as u64),
+            None => summary,
+        }
+    }
+    fn into_shared(self: Box<Self>) -> Result<Arc<dyn SharedSolver>, Box<dyn Solver>> {
+        Err(self)
+    }
 }
 
 fn aei(text: &str) -> usize {
@@ -137,10 +174,23 @@ mod tests {
     use super::*;
 
     fn test(part: u8, input: &str, expect: &str) {
-        let mut solver = Day5::new(input).unwrap();
+        let mut solver = PROBLEM.parse(input).unwrap();
         assert_eq!(solver.solve(part), Ok(expect.into()));
     }
 
+    #[test]
+    fn grammar() {
+        for (input, kind, line, pos) in [
+            ("abc\n\nabc", ErrorKind::UnexpectedEnd, 2, 1),
+            ("abc\naBc", ErrorKind::InvalidCharacter, 2, 2),
+            ("abc d", ErrorKind::InvalidCharacter, 1, 4),
+            ("abcé", ErrorKind::InvalidCharacter, 1, 4),
+        ] {
+            let err = PROBLEM.parse(input).err().unwrap();
+            assert_eq!((err.kind(), err.line(), err.pos()), (kind, line, pos));
+        }
+    }
+
     #[test]
     fn utils() {
         assert_eq!(aei("aei"), 3);